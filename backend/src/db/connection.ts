@@ -1,7 +1,16 @@
 import { Database } from "bun:sqlite";
 import { join } from "path";
 import { homedir } from "os";
-import { mkdirSync, existsSync } from "fs";
+import {
+  copyFileSync,
+  existsSync,
+  mkdirSync,
+  renameSync,
+  rmSync,
+  statSync,
+} from "fs";
+import { runMigrations, SCHEMA_VERSION } from "@/db/migrations";
+import type { DatabaseOptimization } from "@/types";
 
 let db: Database | null = null;
 let lastError: Error | null = null;
@@ -11,7 +20,7 @@ let isInitializing = false;
  * Get database directory path
  * Uses ~/Library/Application Support/rss-reader/ on macOS
  */
-function getDbDir(): string {
+export function getDbDir(): string {
   const dataDir = join(
     homedir(),
     "Library",
@@ -67,7 +76,12 @@ export function initializeDatabase(): boolean {
 
     db = new Database(dbPath, { create: true });
     db.exec("PRAGMA journal_mode = WAL");
+    // Wait for a concurrent writer instead of failing with SQLITE_BUSY
+    db.exec("PRAGMA busy_timeout = 5000");
     initializeSchema(db);
+    // ON DELETE CASCADE needs this; turned on after migrating, since a
+    // table rebuild may copy rows orphaned while it was off
+    db.exec("PRAGMA foreign_keys = ON");
 
     lastError = null;
     console.log("[DB] Database initialized successfully");
@@ -172,6 +186,22 @@ function initializeSchema(database: Database): void {
     )
   `);
 
+  // Create feed title translations cache (one row per language)
+  database.exec(`
+    CREATE TABLE IF NOT EXISTS feed_title_translations (
+      feed_id TEXT NOT NULL,
+      lang TEXT NOT NULL,
+      source_title TEXT NOT NULL,
+      title TEXT NOT NULL,
+      created_at INTEGER DEFAULT (unixepoch()),
+      PRIMARY KEY (feed_id, lang),
+      FOREIGN KEY (feed_id) REFERENCES feeds(id) ON DELETE CASCADE
+    )
+  `);
+
+  // Bring older databases up to the current schema
+  runMigrations(database);
+
   // Create indexes
   database.exec(`
     CREATE INDEX IF NOT EXISTS idx_articles_feed ON articles(feed_id);
@@ -194,3 +224,144 @@ export function closeDatabase(): void {
   lastError = null;
   isInitializing = false;
 }
+
+// Tables a backup must contain to be restored
+const REQUIRED_TABLES = ["feeds", "articles", "settings"];
+
+/**
+ * Copy the live database to destPath
+ * VACUUM INTO writes a consistent snapshot, including changes still in the
+ * WAL, without blocking other connections
+ */
+export function backupDatabase(destPath: string): void {
+  if (existsSync(destPath)) {
+    throw new Error(`File already exists: ${destPath}`);
+  }
+
+  getDatabase().query("VACUUM INTO ?").run(destPath);
+  console.log("[DB] Backed up database to:", destPath);
+}
+
+/**
+ * Check that a file is an intact database with our schema, from this or an
+ * older version of the app
+ */
+function validateBackup(srcPath: string): void {
+  let backup: Database;
+  try {
+    backup = new Database(srcPath, { readonly: true });
+  } catch (error: any) {
+    throw new Error(`Cannot open backup: ${error.message}`);
+  }
+
+  try {
+    const { quick_check: check } = backup
+      .query("PRAGMA quick_check")
+      .get() as { quick_check: string };
+    if (check !== "ok") {
+      throw new Error(`Backup is corrupt: ${check}`);
+    }
+
+    const tables = new Set(
+      (
+        backup
+          .query("SELECT name FROM sqlite_master WHERE type = 'table'")
+          .all() as { name: string }[]
+      ).map((row) => row.name),
+    );
+    const missing = REQUIRED_TABLES.filter((table) => !tables.has(table));
+    if (missing.length > 0) {
+      throw new Error(
+        `Not an RSS Reader backup (missing ${missing.join(", ")})`,
+      );
+    }
+
+    const { user_version: version } = backup
+      .query("PRAGMA user_version")
+      .get() as { user_version: number };
+    if (version > SCHEMA_VERSION) {
+      throw new Error("Backup was made by a newer version of the app");
+    }
+  } catch (error: any) {
+    // Opening succeeds lazily, so non-database files fail on first query
+    if (error.code === "SQLITE_NOTADB") {
+      throw new Error("Not a valid database file");
+    }
+    throw error;
+  } finally {
+    backup.close();
+  }
+}
+
+/**
+ * Replace the database with a backup made by backupDatabase
+ * The current file is kept as data.db.before-restore, and put back if the
+ * backup fails to open
+ */
+export function restoreDatabase(srcPath: string): void {
+  validateBackup(srcPath);
+
+  const dbPath = getDbPath();
+  const previousPath = `${dbPath}.before-restore`;
+
+  // Closing the last connection checkpoints the WAL into the main file
+  closeDatabase();
+  rmSync(`${dbPath}-wal`, { force: true });
+  rmSync(`${dbPath}-shm`, { force: true });
+
+  if (existsSync(dbPath)) {
+    renameSync(dbPath, previousPath);
+  }
+  copyFileSync(srcPath, dbPath);
+
+  // Older backups are migrated on open
+  if (!initializeDatabase()) {
+    const error = lastError;
+    closeDatabase();
+    renameSync(previousPath, dbPath);
+    initializeDatabase();
+    throw error || new Error("Failed to open restored database");
+  }
+
+  console.log("[DB] Restored database from:", srcPath);
+}
+
+/**
+ * On-disk size of the database, including its WAL
+ */
+function getDbSize(): number {
+  const dbPath = getDbPath();
+  return [dbPath, `${dbPath}-wal`]
+    .filter((path) => existsSync(path))
+    .reduce((total, path) => total + statSync(path).size, 0);
+}
+
+/**
+ * Check integrity, then VACUUM and optimize to give space freed by deleted
+ * articles back to the file system. A damaged database is left untouched
+ */
+export function optimizeDatabase(): DatabaseOptimization {
+  const database = getDatabase();
+  const sizeBefore = getDbSize();
+
+  const problems = (
+    database.query("PRAGMA integrity_check").all() as {
+      integrity_check: string;
+    }[]
+  ).map((row) => row.integrity_check);
+  const integrity = problems.join("\n");
+
+  if (integrity === "ok") {
+    database.exec("VACUUM");
+    database.exec("PRAGMA optimize");
+    // VACUUM writes through the WAL; fold it back into the main file
+    database.exec("PRAGMA wal_checkpoint(TRUNCATE)");
+  } else {
+    console.error("[DB] Integrity check failed:", integrity);
+  }
+
+  const sizeAfter = getDbSize();
+  console.log(`[DB] Optimized database: ${sizeBefore} -> ${sizeAfter} bytes`);
+
+  return { integrity, sizeBefore, sizeAfter };
+}
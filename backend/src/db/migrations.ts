@@ -0,0 +1,224 @@
+import type { Database } from "bun:sqlite";
+
+type Migration = (database: Database) => void;
+
+/**
+ * Ordered schema migrations, applied on top of the base tables created by
+ * initializeSchema. Migration N brings the database to user_version N, so
+ * entries must only ever be appended, never reordered or edited.
+ */
+const MIGRATIONS: Migration[] = [
+  // 1: columns added before versioning existed; ensureColumn keeps this safe
+  // for databases that already picked some of them up
+  (database) => {
+    ensureColumn(database, "articles", "is_kept", "INTEGER DEFAULT 0");
+    ensureColumn(database, "articles", "read_at", "INTEGER");
+    ensureColumn(database, "feeds", "prefer_summary", "INTEGER DEFAULT 0");
+    ensureColumn(database, "feeds", "title_translated", "TEXT");
+    ensureColumn(database, "feeds", "etag", "TEXT");
+    ensureColumn(database, "feeds", "last_modified", "TEXT");
+  },
+  // 2: entry GUIDs for deduplication
+  (database) => {
+    database.exec(`
+      ALTER TABLE articles ADD COLUMN guid TEXT;
+      CREATE INDEX idx_articles_feed_guid ON articles(feed_id, guid);
+    `);
+  },
+  // 3: podcast and other media enclosures
+  (database) => {
+    database.exec(`
+      ALTER TABLE articles ADD COLUMN enclosure_url TEXT;
+      ALTER TABLE articles ADD COLUMN enclosure_type TEXT;
+      ALTER TABLE articles ADD COLUMN enclosure_length INTEGER;
+    `);
+  },
+  // 4: lead image shown as the article thumbnail
+  (database) => {
+    database.exec("ALTER TABLE articles ADD COLUMN image_url TEXT");
+  },
+  // 5: per-feed request headers, stored as a JSON object
+  (database) => {
+    database.exec("ALTER TABLE feeds ADD COLUMN headers TEXT");
+  },
+  // 6: refresh health
+  (database) => {
+    database.exec(`
+      ALTER TABLE feeds ADD COLUMN last_error TEXT;
+      ALTER TABLE feeds ADD COLUMN last_fetched_at INTEGER;
+    `);
+  },
+  // 7: translation results keyed by a hash of (text, target language)
+  (database) => {
+    database.exec(`
+      CREATE TABLE translation_cache (
+        hash TEXT PRIMARY KEY,
+        target_lang TEXT NOT NULL,
+        content TEXT NOT NULL,
+        created_at INTEGER DEFAULT (unixepoch())
+      )
+    `);
+  },
+  // 8: feed language, declared or detected
+  (database) => {
+    database.exec("ALTER TABLE feeds ADD COLUMN language TEXT");
+  },
+  // 9: keyset pagination on (pub_date, id)
+  (database) => {
+    database.exec(`
+      CREATE INDEX idx_articles_date_id ON articles(pub_date, id);
+      CREATE INDEX idx_articles_feed_date_id
+        ON articles(feed_id, pub_date, id);
+    `);
+  },
+  // 10: keyword filter rules applied to new articles
+  (database) => {
+    database.exec(`
+      CREATE TABLE filter_rules (
+        id TEXT PRIMARY KEY,
+        pattern TEXT NOT NULL,
+        field TEXT NOT NULL,
+        action TEXT NOT NULL,
+        feed_id TEXT,
+        created_at INTEGER DEFAULT (unixepoch())
+      )
+    `);
+  },
+  // 11: word count for reading time estimates
+  (database) => {
+    database.exec("ALTER TABLE articles ADD COLUMN word_count INTEGER");
+  },
+  // 12: per-feed auto-refresh interval
+  (database) => {
+    database.exec(
+      "ALTER TABLE feeds ADD COLUMN refresh_interval_minutes INTEGER",
+    );
+  },
+  // 13: user-defined feed order
+  (database) => {
+    database.exec("ALTER TABLE feeds ADD COLUMN sort_order INTEGER");
+  },
+  // 14: HTTP basic auth; password is encrypted by services/credentials
+  (database) => {
+    database.exec(`
+      ALTER TABLE feeds ADD COLUMN username TEXT;
+      ALTER TABLE feeds ADD COLUMN password TEXT;
+    `);
+  },
+  // 15: insert new articles of high-volume feeds as already read
+  (database) => {
+    database.exec(
+      "ALTER TABLE feeds ADD COLUMN auto_mark_read INTEGER DEFAULT 0",
+    );
+  },
+  // 16: link lookups for global_dedupe
+  (database) => {
+    database.exec("CREATE INDEX idx_articles_link ON articles(link)");
+  },
+  // 17: date string as the feed wrote it, for debugging date parsing
+  (database) => {
+    database.exec("ALTER TABLE articles ADD COLUMN pub_date_raw TEXT");
+  },
+  // 18: duration of the last fetch, with record_fetch_timing on
+  (database) => {
+    database.exec("ALTER TABLE feeds ADD COLUMN last_fetch_ms INTEGER");
+  },
+  // 19: Retry-After from a 429, as unix time; the scheduler waits for it
+  (database) => {
+    database.exec("ALTER TABLE feeds ADD COLUMN retry_after INTEGER");
+  },
+  // 20: one saved translation per article and target language. Existing
+  // rows were made by the article view, which always translated to Chinese
+  (database) => {
+    database.exec(`
+      CREATE TABLE translations_new (
+        article_id TEXT NOT NULL,
+        target_lang TEXT NOT NULL,
+        content TEXT NOT NULL,
+        created_at INTEGER DEFAULT (unixepoch()),
+        PRIMARY KEY (article_id, target_lang),
+        FOREIGN KEY (article_id) REFERENCES articles(id) ON DELETE CASCADE
+      )
+    `);
+    database.exec(`
+      INSERT INTO translations_new
+        (article_id, target_lang, content, created_at)
+      SELECT article_id, 'zh', content, created_at FROM translations
+    `);
+    database.exec("DROP TABLE translations");
+    database.exec("ALTER TABLE translations_new RENAME TO translations");
+  },
+  // 21: when an article was last changed by an edit at the source
+  (database) => {
+    database.exec("ALTER TABLE articles ADD COLUMN updated_at INTEGER");
+  },
+  // 22: hash of the entry as the feed last sent it, so edits at the source
+  // are told apart from local changes; full_content marks articles whose
+  // content was replaced by the extracted web page
+  (database) => {
+    database.exec("ALTER TABLE articles ADD COLUMN source_hash TEXT");
+    database.exec(
+      "ALTER TABLE articles ADD COLUMN full_content INTEGER DEFAULT 0",
+    );
+  },
+  // 23: translated feed titles are read from the per-language cache, which
+  // is keyed like translations; feeds.title_translated held only the last
+  // language asked for
+  (database) => {
+    database.exec(`
+      ALTER TABLE feed_title_translations RENAME COLUMN lang TO target_lang;
+      ALTER TABLE feeds DROP COLUMN title_translated;
+    `);
+  },
+  // 24: rows left behind by deletes made before foreign keys were enforced
+  (database) => {
+    database.exec(`
+      DELETE FROM translations
+      WHERE article_id NOT IN (SELECT id FROM articles);
+      DELETE FROM feed_title_translations
+      WHERE feed_id NOT IN (SELECT id FROM feeds);
+    `);
+  },
+];
+
+/** user_version of a fully migrated database */
+export const SCHEMA_VERSION = MIGRATIONS.length;
+
+/**
+ * Apply every migration newer than the database's PRAGMA user_version
+ * Each step runs in its own transaction together with the version bump
+ */
+export function runMigrations(database: Database): void {
+  const { user_version: current } = database
+    .query("PRAGMA user_version")
+    .get() as { user_version: number };
+
+  for (let version = current + 1; version <= MIGRATIONS.length; version++) {
+    const migrate = database.transaction(() => {
+      MIGRATIONS[version - 1](database);
+      database.exec(`PRAGMA user_version = ${version}`);
+    });
+    migrate();
+    console.log(`[DB] Migrated schema to version ${version}`);
+  }
+}
+
+/**
+ * Add a column to an existing table if it is missing
+ * CREATE TABLE IF NOT EXISTS never alters tables from older databases
+ */
+function ensureColumn(
+  database: Database,
+  table: string,
+  column: string,
+  definition: string,
+): void {
+  const columns = database.query(`PRAGMA table_info(${table})`).all() as {
+    name: string;
+  }[];
+
+  if (!columns.some((c) => c.name === column)) {
+    database.exec(`ALTER TABLE ${table} ADD COLUMN ${column} ${definition}`);
+    console.log(`[DB] Added column ${table}.${column}`);
+  }
+}
@@ -0,0 +1,70 @@
+import type { Database } from "bun:sqlite";
+
+/**
+ * Read a raw setting value, or null when it has never been set
+ */
+export function getSetting(db: Database, key: string): string | null {
+  const query = db.query("SELECT value FROM settings WHERE key = ?");
+  const result = query.get(key) as { value: string } | null;
+  return result?.value ?? null;
+}
+
+/**
+ * Read a setting, falling back to defaultValue when it is unset or blank
+ */
+export function getSettingOr(
+  db: Database,
+  key: string,
+  defaultValue: string,
+): string {
+  const value = getSetting(db, key);
+  return value?.trim() ? value : defaultValue;
+}
+
+/**
+ * Read a flag stored as "true"/"false" or "1"/"0"; anything else, including
+ * no value, gives defaultValue
+ */
+export function getBoolSetting(
+  db: Database,
+  key: string,
+  defaultValue: boolean,
+): boolean {
+  const value = getSetting(db, key)?.trim().toLowerCase();
+  if (value === "true" || value === "1") return true;
+  if (value === "false" || value === "0") return false;
+  return defaultValue;
+}
+
+/**
+ * Read an integer setting. Values that don't parse or are below min give
+ * defaultValue
+ */
+export function getIntSetting(
+  db: Database,
+  key: string,
+  defaultValue: number,
+  min = 0,
+): number {
+  const value = parseInt(getSetting(db, key) || "");
+  return value >= min ? value : defaultValue;
+}
+
+/**
+ * Read a list setting stored either as a JSON array or as comma/newline
+ * separated text
+ */
+export function getListSetting(db: Database, key: string): string[] {
+  const value = getSetting(db, key);
+  if (!value) return [];
+
+  let items: unknown[];
+  try {
+    const parsed = JSON.parse(value);
+    items = Array.isArray(parsed) ? parsed : [parsed];
+  } catch {
+    items = value.split(/[,\n]/);
+  }
+
+  return items.map((item) => String(item).trim()).filter(Boolean);
+}
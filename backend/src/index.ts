@@ -7,6 +7,10 @@ import feedsRouter from "./routes/feeds";
 import articlesRouter from "./routes/articles";
 import settingsRouter from "./routes/settings";
 import translationRouter from "./routes/translation";
+import filterRulesRouter from "./routes/filterRules";
+import statsRouter from "./routes/stats";
+import databaseRouter from "./routes/database";
+import { startScheduler, stopScheduler } from "@/services/scheduler";
 
 const app = new Hono();
 
@@ -36,6 +40,9 @@ app.route("/api/articles", articlesRouter);
 app.route("/api/settings", settingsRouter);
 app.route("/api/translate", translationRouter);
 app.route("/api/translations", translationRouter);
+app.route("/api/filter-rules", filterRulesRouter);
+app.route("/api/stats", statsRouter);
+app.route("/api/database", databaseRouter);
 
 // Get port from args or use random port
 const args = Bun.argv.slice(2);
@@ -58,6 +65,10 @@ if (port === 0) {
   console.log(`PORT:${server.port}`);
 }
 
+// Background auto-refresh, driven by auto_refresh_interval_minutes and
+// per-feed intervals
+startScheduler();
+
 // Global error handlers to prevent crashes
 process.on("uncaughtException", (error) => {
   console.error("[Server] Uncaught Exception:", error);
@@ -72,12 +83,14 @@ process.on("unhandledRejection", (reason, promise) => {
 // Graceful shutdown
 process.on("SIGINT", () => {
   console.log("\n👋 Shutting down gracefully...");
+  stopScheduler();
   server.stop();
   process.exit(0);
 });
 
 process.on("SIGTERM", () => {
   console.log("\n👋 Shutting down gracefully...");
+  stopScheduler();
   server.stop();
   process.exit(0);
 });
@@ -1,7 +1,17 @@
-import { Hono } from "hono";
+import { Hono, type Context } from "hono";
 import { getDatabase } from "@/db/connection";
-import type { Article, ArticleFilter } from "@/types";
+import { getListSetting } from "@/db/settings";
+import type { Article, ArticleFilter, ArticleSort } from "@/types";
 import { htmlToMarkdown } from "@/utils/htmlToMarkdown";
+import { sanitizeHtml } from "@/utils/sanitize";
+import { chunk, placeholders } from "@/utils/sql";
+import { highlightKeywords } from "@/utils/highlight";
+import {
+  articlesToMarkdown,
+  type ExportedArticle,
+} from "@/utils/markdownExport";
+import { cleanupOldArticles } from "@/services/retention";
+import { fetchFullContent } from "@/services/rss";
 
 const app = new Hono();
 
@@ -20,68 +30,640 @@ function isHtmlContent(text: string): boolean {
 function ensureMarkdown(content: string): string {
   if (!content) return content;
 
-  // If content contains HTML tags, convert it
+  // If content contains HTML tags (e.g. rows stored before conversion),
+  // sanitize and convert it
   if (isHtmlContent(content)) {
-    return htmlToMarkdown(content);
+    return htmlToMarkdown(sanitizeHtml(content));
   }
 
   // Already Markdown (or plain text)
   return content;
 }
 
+const ARTICLE_COLUMNS = `
+  id, feed_id as feedId, title, link, content, summary, author,
+  pub_date as pubDate, pub_date_raw as pubDateRaw,
+  is_read as isRead, is_starred as isStarred,
+  is_kept as isKept, read_at as readAt,
+  enclosure_url as enclosureUrl, enclosure_type as enclosureType,
+  enclosure_length as enclosureLength, image_url as imageUrl,
+  word_count as wordCount, fetched_at as fetchedAt, updated_at as updatedAt
+`;
+
+// ORDER BY clauses for each sort option; user input only selects a key
+const ARTICLE_SORTS: Record<ArticleSort, string> = {
+  date_desc: "pub_date DESC",
+  date_asc: "pub_date ASC",
+  fetched_desc: "fetched_at DESC",
+  title_asc: "title COLLATE NOCASE ASC",
+};
+
+// Article as selected with ARTICLE_COLUMNS; SQLite stores flags as 0/1
+type ArticleRow = Omit<Article, "isRead" | "isStarred" | "isKept"> & {
+  isRead: number;
+  isStarred: number;
+  isKept: number;
+};
+
+/**
+ * Convert a row for the API: flags become booleans, and HTML content is
+ * converted to Markdown on-the-fly for legacy data
+ */
+function processArticle(article: ArticleRow): Article {
+  return {
+    ...article,
+    isRead: article.isRead === 1,
+    isStarred: article.isStarred === 1,
+    isKept: article.isKept === 1,
+    content: ensureMarkdown(article.content),
+    summary: ensureMarkdown(article.summary || ""),
+  };
+}
+
+interface ArticleQueryFilters {
+  feedId?: string;
+  filter?: ArticleFilter;
+  /** Publication window, unix seconds (inclusive) */
+  after?: number;
+  before?: number;
+  /** Text that must appear in the title or content */
+  q?: string;
+}
+
+/**
+ * WHERE conditions shared by the article listing and counting endpoints
+ */
+function buildArticleConditions(filters: ArticleQueryFilters): {
+  conditions: string[];
+  params: any[];
+} {
+  const conditions: string[] = [];
+  const params: any[] = [];
+
+  if (filters.feedId) {
+    conditions.push("feed_id = ?");
+    params.push(filters.feedId);
+  }
+
+  if (filters.filter === "unread") {
+    conditions.push("is_read = 0");
+  } else if (filters.filter === "starred") {
+    conditions.push("is_starred = 1");
+  }
+
+  if (filters.after !== undefined) {
+    conditions.push("pub_date >= ?");
+    params.push(filters.after);
+  }
+
+  if (filters.before !== undefined) {
+    conditions.push("pub_date <= ?");
+    params.push(filters.before);
+  }
+
+  const q = filters.q?.trim();
+  if (q) {
+    // Match % and _ literally
+    const pattern = `%${q.replace(/[\\%_]/g, "\\$&")}%`;
+    conditions.push(
+      "(title LIKE ? ESCAPE '\\' OR content LIKE ? ESCAPE '\\')",
+    );
+    params.push(pattern, pattern);
+  }
+
+  return { conditions, params };
+}
+
 // GET /api/articles - Get articles with optional filters
 app.get("/", (c) => {
   try {
     const feedId = c.req.query("feedId");
     const filter = c.req.query("filter") as ArticleFilter | undefined;
+    const sort = (c.req.query("sort") || "date_desc") as ArticleSort;
     const limit = parseInt(c.req.query("limit") || "100");
     const offset = parseInt(c.req.query("offset") || "0");
+    // Optional publication window, unix seconds (inclusive)
+    const after = c.req.query("after");
+    const before = c.req.query("before");
+
+    if ((after && isNaN(Number(after))) || (before && isNaN(Number(before)))) {
+      return c.json({ error: "after and before must be unix timestamps" }, 400);
+    }
+
+    if (!Object.hasOwn(ARTICLE_SORTS, sort)) {
+      return c.json({ error: `Unknown sort: ${sort}` }, 400);
+    }
 
     const db = getDatabase();
 
-    let queryStr = `
-      SELECT 
-        id, feed_id as feedId, title, link, content, summary, author, 
-        pub_date as pubDate, is_read as isRead, is_starred as isStarred, 
-        fetched_at as fetchedAt
-      FROM articles
-    `;
+    let queryStr = `SELECT ${ARTICLE_COLUMNS} FROM articles`;
 
-    const conditions: string[] = [];
+    const { conditions, params } = buildArticleConditions({
+      feedId,
+      filter,
+      after: after ? Number(after) : undefined,
+      before: before ? Number(before) : undefined,
+      q: c.req.query("q"),
+    });
+
+    if (conditions.length > 0) {
+      queryStr += " WHERE " + conditions.join(" AND ");
+    }
+
+    // id keeps pagination stable when sort keys tie
+    queryStr += ` ORDER BY ${ARTICLE_SORTS[sort]}, id LIMIT ? OFFSET ?`;
+    params.push(limit, offset);
+
+    const query = db.query(queryStr);
+    const articles = query.all(...params) as ArticleRow[];
+
+    return c.json(articles.map(processArticle));
+  } catch (error: any) {
+    console.error("[Articles] Failed to get articles:", error.message);
+    return c.json({ error: error.message || "Failed to get articles" }, 500);
+  }
+});
+
+// GET /api/articles/count - Number of articles matching the list filters
+app.get("/count", (c) => {
+  try {
+    const after = c.req.query("after");
+    const before = c.req.query("before");
+
+    if ((after && isNaN(Number(after))) || (before && isNaN(Number(before)))) {
+      return c.json({ error: "after and before must be unix timestamps" }, 400);
+    }
+
+    const { conditions, params } = buildArticleConditions({
+      feedId: c.req.query("feedId"),
+      filter: c.req.query("filter") as ArticleFilter | undefined,
+      after: after ? Number(after) : undefined,
+      before: before ? Number(before) : undefined,
+      q: c.req.query("q"),
+    });
+    const where = conditions.length ? `WHERE ${conditions.join(" AND ")}` : "";
+
+    const db = getDatabase();
+    const { count } = db
+      .query(`SELECT COUNT(*) as count FROM articles ${where}`)
+      .get(...params) as { count: number };
+
+    return c.json({ count });
+  } catch (error: any) {
+    console.error("[Articles] Failed to count articles:", error.message);
+    return c.json({ error: error.message || "Failed to count articles" }, 500);
+  }
+});
+
+// GET /api/articles/page - Keyset-paginated articles, newest first
+// cursor is the nextCursor of the previous page ("<pubDate>:<id>")
+app.get("/page", (c) => {
+  try {
+    const feedId = c.req.query("feedId");
+    const filter = c.req.query("filter") as ArticleFilter | undefined;
+    const cursor = c.req.query("cursor");
+    const limit = parseInt(c.req.query("limit") || "50");
+
+    const { conditions, params } = buildArticleConditions({
+      feedId,
+      filter,
+      q: c.req.query("q"),
+    });
+
+    if (cursor) {
+      const separator = cursor.indexOf(":");
+      const pubDate = Number(cursor.slice(0, separator));
+      if (separator < 0 || isNaN(pubDate)) {
+        return c.json({ error: "Invalid cursor" }, 400);
+      }
+      conditions.push("(pub_date, id) < (?, ?)");
+      params.push(pubDate, cursor.slice(separator + 1));
+    }
+
+    const where = conditions.length ? `WHERE ${conditions.join(" AND ")}` : "";
+    const db = getDatabase();
+    const articles = db
+      .query(
+        `SELECT ${ARTICLE_COLUMNS} FROM articles ${where}
+         ORDER BY pub_date DESC, id DESC LIMIT ?`,
+      )
+      .all(...params, limit) as ArticleRow[];
+
+    // A short page means there is nothing after it
+    const last = articles[articles.length - 1];
+    const nextCursor =
+      articles.length === limit && last ? `${last.pubDate}:${last.id}` : null;
+
+    return c.json({ articles: articles.map(processArticle), nextCursor });
+  } catch (error: any) {
+    console.error("[Articles] Failed to get article page:", error.message);
+    return c.json({ error: error.message || "Failed to get articles" }, 500);
+  }
+});
+
+// GET /api/articles/starred/markdown - Starred articles as a Markdown document
+app.get("/starred/markdown", (c) => {
+  try {
+    const db = getDatabase();
+    const articles = db
+      .query(
+        `SELECT a.title, a.link, a.author, a.pub_date as pubDate, a.summary,
+           COALESCE(f.title, 'Unknown Feed') as feedTitle
+         FROM articles a LEFT JOIN feeds f ON f.id = a.feed_id
+         WHERE a.is_starred = 1
+         ORDER BY feedTitle COLLATE NOCASE, a.pub_date DESC`,
+      )
+      .all() as ExportedArticle[];
+
+    const markdown = articlesToMarkdown("Starred Articles", articles);
+    return c.body(markdown, 200, {
+      "Content-Type": "text/markdown; charset=utf-8",
+    });
+  } catch (error: any) {
+    console.error("[Articles] Failed to export starred:", error.message);
+    return c.json(
+      { error: error.message || "Failed to export starred articles" },
+      500,
+    );
+  }
+});
+
+// GET /api/articles/contextual - Unread articles plus the most recently read
+app.get("/contextual", (c) => {
+  try {
+    const feedId = c.req.query("feedId");
+    const recentReadCount = parseInt(c.req.query("recentReadCount") || "5");
+    const limit = parseInt(c.req.query("limit") || "100");
+
+    const db = getDatabase();
+
+    const feedCondition = feedId ? " AND feed_id = ?" : "";
+    const feedParams = feedId ? [feedId] : [];
+
+    const unread = db
+      .query(
+        `SELECT ${ARTICLE_COLUMNS} FROM articles
+         WHERE is_read = 0${feedCondition}
+         ORDER BY pub_date DESC LIMIT ?`,
+      )
+      .all(...feedParams, limit) as ArticleRow[];
+
+    const recentRead = db
+      .query(
+        `SELECT ${ARTICLE_COLUMNS} FROM articles
+         WHERE is_read = 1${feedCondition}
+         ORDER BY read_at DESC, pub_date DESC LIMIT ?`,
+      )
+      .all(...feedParams, recentReadCount) as ArticleRow[];
+
+    const articles = [...unread, ...recentRead].sort(
+      (a, b) => (b.pubDate || 0) - (a.pubDate || 0),
+    );
+
+    return c.json(articles.map(processArticle));
+  } catch (error: any) {
+    console.error(
+      "[Articles] Failed to get contextual articles:",
+      error.message,
+    );
+    return c.json({ error: error.message || "Failed to get articles" }, 500);
+  }
+});
+
+// GET /api/articles/first-unread - Get the article to resume reading from
+app.get("/first-unread", (c) => {
+  try {
+    const feedId = c.req.query("feedId");
+    const order = c.req.query("order") === "newest" ? "DESC" : "ASC";
+
+    const db = getDatabase();
+
+    let queryStr = `SELECT ${ARTICLE_COLUMNS} FROM articles WHERE is_read = 0`;
     const params: any[] = [];
 
     if (feedId) {
-      conditions.push("feed_id = ?");
+      queryStr += " AND feed_id = ?";
       params.push(feedId);
     }
 
-    if (filter === "unread") {
-      conditions.push("is_read = 0");
-    } else if (filter === "starred") {
-      conditions.push("is_starred = 1");
+    queryStr += ` ORDER BY pub_date ${order}, id ${order} LIMIT 1`;
+
+    const article = db.query(queryStr).get(...params) as ArticleRow | null;
+
+    return c.json(article ? processArticle(article) : null);
+  } catch (error: any) {
+    console.error("[Articles] Failed to get first unread:", error.message);
+    return c.json(
+      { error: error.message || "Failed to get first unread article" },
+      500,
+    );
+  }
+});
+
+/**
+ * The unread article next to (pubDate, id) in the list's newest-first
+ * order: "next" is the older neighbour, "prev" the newer one
+ */
+function getAdjacentUnread(
+  direction: "next" | "prev",
+  position: { feedId?: string; pubDate: number; id: string },
+): Article | null {
+  const [comparison, order] =
+    direction === "next" ? ["<", "DESC"] : [">", "ASC"];
+  const conditions = ["is_read = 0", `(pub_date, id) ${comparison} (?, ?)`];
+  const params: any[] = [position.pubDate, position.id];
+
+  if (position.feedId) {
+    conditions.push("feed_id = ?");
+    params.push(position.feedId);
+  }
+
+  const article = getDatabase()
+    .query(
+      `SELECT ${ARTICLE_COLUMNS} FROM articles
+       WHERE ${conditions.join(" AND ")}
+       ORDER BY pub_date ${order}, id ${order} LIMIT 1`,
+    )
+    .get(...params) as ArticleRow | null;
+
+  return article ? processArticle(article) : null;
+}
+
+/**
+ * Shared handler for next-unread and prev-unread
+ */
+function adjacentUnreadRoute(direction: "next" | "prev") {
+  return (c: Context) => {
+    try {
+      const pubDate = Number(c.req.query("pubDate"));
+      const id = c.req.query("id");
+
+      if (!id || isNaN(pubDate)) {
+        return c.json({ error: "pubDate and id are required" }, 400);
+      }
+
+      const article = getAdjacentUnread(direction, {
+        feedId: c.req.query("feedId"),
+        pubDate,
+        id,
+      });
+
+      return c.json(article);
+    } catch (error: any) {
+      console.error("[Articles] Failed to get unread article:", error.message);
+      return c.json(
+        { error: error.message || "Failed to get unread article" },
+        500,
+      );
     }
+  };
+}
 
-    if (conditions.length > 0) {
-      queryStr += " WHERE " + conditions.join(" AND ");
+// GET /api/articles/next-unread - Unread article after the given one
+app.get("/next-unread", adjacentUnreadRoute("next"));
+
+// GET /api/articles/prev-unread - Unread article before the given one
+app.get("/prev-unread", adjacentUnreadRoute("prev"));
+
+// POST /api/articles/mark-seen - Mark a batch of articles as read
+app.post("/mark-seen", async (c) => {
+  try {
+    const { ids } = await c.req.json();
+
+    if (!Array.isArray(ids)) {
+      return c.json({ error: "ids must be an array" }, 400);
     }
 
-    queryStr += " ORDER BY pub_date DESC LIMIT ? OFFSET ?";
-    params.push(limit, offset);
+    const db = getDatabase();
+    const now = Math.floor(Date.now() / 1000);
 
-    const query = db.query(queryStr);
-    const articles = query.all(...params) as Article[];
+    // Already-read articles are left untouched so read_at keeps its value
+    const markSeen = db.transaction((batches: string[][]) => {
+      for (const batch of batches) {
+        db.query(
+          `UPDATE articles SET is_read = 1, read_at = ?
+           WHERE is_read = 0 AND id IN (${placeholders(batch.length)})`,
+        ).run(now, ...batch);
+      }
+    });
+    markSeen(chunk(ids as string[]));
+
+    return c.json({ success: true });
+  } catch (error: any) {
+    console.error("[Articles] Failed to mark articles seen:", error.message);
+    return c.json({ error: error.message || "Failed to update articles" }, 400);
+  }
+});
+
+// POST /api/articles/mark-read - Mark a selection of articles read/unread
+app.post("/mark-read", async (c) => {
+  try {
+    const { ids, read } = await c.req.json();
+
+    if (!Array.isArray(ids)) {
+      return c.json({ error: "ids must be an array" }, 400);
+    }
+
+    const db = getDatabase();
+    const readAt = read ? Math.floor(Date.now() / 1000) : null;
 
-    // Convert HTML content to Markdown on-the-fly for legacy data
-    const processedArticles = articles.map((article) => ({
-      ...article,
-      content: ensureMarkdown(article.content),
-      summary: ensureMarkdown(article.summary || ""),
-    }));
+    const markRead = db.transaction((batches: string[][]) => {
+      let count = 0;
+      for (const batch of batches) {
+        count += db
+          .query(
+            `UPDATE articles SET is_read = ?, read_at = ?
+             WHERE id IN (${placeholders(batch.length)})`,
+          )
+          .run(read ? 1 : 0, readAt, ...batch).changes;
+      }
+      return count;
+    });
 
-    return c.json(processedArticles);
+    return c.json({ count: markRead(chunk(ids as string[])) });
   } catch (error: any) {
-    console.error("[Articles] Failed to get articles:", error.message);
-    return c.json({ error: error.message || "Failed to get articles" }, 500);
+    console.error("[Articles] Failed to mark articles read:", error.message);
+    return c.json({ error: error.message || "Failed to update articles" }, 400);
+  }
+});
+
+// POST /api/articles/star - Star or unstar a selection of articles
+app.post("/star", async (c) => {
+  try {
+    const { ids, starred } = await c.req.json();
+
+    if (!Array.isArray(ids)) {
+      return c.json({ error: "ids must be an array" }, 400);
+    }
+
+    const db = getDatabase();
+
+    const star = db.transaction((batches: string[][]) => {
+      let count = 0;
+      for (const batch of batches) {
+        count += db
+          .query(
+            `UPDATE articles SET is_starred = ?
+             WHERE id IN (${placeholders(batch.length)})`,
+          )
+          .run(starred ? 1 : 0, ...batch).changes;
+      }
+      return count;
+    });
+
+    return c.json({ count: star(chunk(ids as string[])) });
+  } catch (error: any) {
+    console.error("[Articles] Failed to star articles:", error.message);
+    return c.json({ error: error.message || "Failed to update articles" }, 400);
+  }
+});
+
+// POST /api/articles/delete - Delete a selection of articles
+app.post("/delete", async (c) => {
+  try {
+    const { ids } = await c.req.json();
+
+    if (!Array.isArray(ids)) {
+      return c.json({ error: "ids must be an array" }, 400);
+    }
+
+    const db = getDatabase();
+
+    // An explicit selection also removes kept articles
+    const deleteArticles = db.transaction((batches: string[][]) => {
+      let count = 0;
+      for (const batch of batches) {
+        const list = placeholders(batch.length);
+        db.query(`DELETE FROM translations WHERE article_id IN (${list})`).run(
+          ...batch,
+        );
+        count += db
+          .query(`DELETE FROM articles WHERE id IN (${list})`)
+          .run(...batch).changes;
+      }
+      return count;
+    });
+
+    return c.json({ count: deleteArticles(chunk(ids as string[])) });
+  } catch (error: any) {
+    console.error("[Articles] Failed to delete articles:", error.message);
+    return c.json(
+      { error: error.message || "Failed to delete articles" },
+      400,
+    );
+  }
+});
+
+// POST /api/articles/cleanup - Delete old read articles
+app.post("/cleanup", async (c) => {
+  try {
+    const { days, keepStarred = true } = await c.req.json();
+
+    if (typeof days !== "number" || days < 0) {
+      return c.json({ error: "days must be a non-negative number" }, 400);
+    }
+
+    const db = getDatabase();
+    const count = cleanupOldArticles(db, days, keepStarred);
+
+    return c.json({ count });
+  } catch (error: any) {
+    console.error("[Articles] Failed to clean up articles:", error.message);
+    return c.json(
+      { error: error.message || "Failed to clean up articles" },
+      400,
+    );
+  }
+});
+
+// GET /api/articles/:id - Get a single article, or null if it doesn't exist
+app.get("/:id", (c) => {
+  try {
+    const { id } = c.req.param();
+    const db = getDatabase();
+
+    const article = db
+      .query(`SELECT ${ARTICLE_COLUMNS} FROM articles WHERE id = ?`)
+      .get(id) as ArticleRow | null;
+
+    return c.json(article ? processArticle(article) : null);
+  } catch (error: any) {
+    console.error("[Articles] Failed to get article:", error.message);
+    return c.json({ error: error.message || "Failed to get article" }, 500);
+  }
+});
+
+// GET /api/articles/:id/highlighted - Article content with keywords marked
+app.get("/:id/highlighted", (c) => {
+  try {
+    const { id } = c.req.param();
+    const db = getDatabase();
+
+    const result = db
+      .query("SELECT content FROM articles WHERE id = ?")
+      .get(id) as { content: string | null } | null;
+
+    if (!result) {
+      return c.json({ error: "Article not found" }, 404);
+    }
+
+    const keywords = getListSetting(db, "highlight_keywords");
+    const content = highlightKeywords(
+      ensureMarkdown(result.content || ""),
+      keywords,
+    );
+
+    return c.json({ content });
+  } catch (error: any) {
+    console.error("[Articles] Failed to highlight article:", error.message);
+    return c.json(
+      { error: error.message || "Failed to highlight article" },
+      500,
+    );
+  }
+});
+
+// POST /api/articles/:id/full-content - Replace a truncated article's
+// content with the body extracted from its web page
+app.post("/:id/full-content", async (c) => {
+  try {
+    const { id } = c.req.param();
+    const db = getDatabase();
+
+    const article = db
+      .query("SELECT link FROM articles WHERE id = ?")
+      .get(id) as { link: string } | null;
+
+    if (!article) {
+      return c.json({ error: "Article not found" }, 404);
+    }
+
+    let extracted;
+    try {
+      extracted = await fetchFullContent(article.link);
+    } catch (error: any) {
+      // The page could not be fetched or had no article body
+      console.error("[Articles] Failed to fetch full content:", error.message);
+      return c.json(
+        { error: error.message || "Failed to fetch full content" },
+        502,
+      );
+    }
+    const { content, wordCount } = extracted;
+
+    // Flagged so update_existing_articles doesn't restore the feed's text
+    db.query(
+      `UPDATE articles SET content = ?, word_count = ?, full_content = 1
+       WHERE id = ?`,
+    ).run(content, wordCount, id);
+
+    return c.json({ content, wordCount });
+  } catch (error: any) {
+    console.error("[Articles] Failed to save full content:", error.message);
+    return c.json(
+      { error: error.message || "Failed to save full content" },
+      500,
+    );
   }
 });
 
@@ -93,8 +675,10 @@ app.patch("/:id/read", async (c) => {
 
     const db = getDatabase();
 
-    const query = db.query("UPDATE articles SET is_read = ? WHERE id = ?");
-    query.run(read ? 1 : 0, id);
+    const query = db.query(
+      "UPDATE articles SET is_read = ?, read_at = ? WHERE id = ?",
+    );
+    query.run(read ? 1 : 0, read ? Math.floor(Date.now() / 1000) : null, id);
 
     return c.json({ success: true });
   } catch (error: any) {
@@ -121,4 +705,22 @@ app.patch("/:id/starred", async (c) => {
   }
 });
 
+// PATCH /api/articles/:id/kept - Exempt article from pruning
+app.patch("/:id/kept", async (c) => {
+  try {
+    const { id } = c.req.param();
+    const { kept } = await c.req.json();
+
+    const db = getDatabase();
+
+    const query = db.query("UPDATE articles SET is_kept = ? WHERE id = ?");
+    query.run(kept ? 1 : 0, id);
+
+    return c.json({ success: true });
+  } catch (error: any) {
+    console.error("[Articles] Failed to set article kept:", error.message);
+    return c.json({ error: error.message || "Failed to update article" }, 400);
+  }
+});
+
 export default app;
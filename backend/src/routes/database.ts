@@ -0,0 +1,73 @@
+import { Hono } from "hono";
+import { existsSync } from "fs";
+import { resolve } from "path";
+import {
+  backupDatabase,
+  optimizeDatabase,
+  restoreDatabase,
+} from "@/db/connection";
+
+const app = new Hono();
+
+// POST /api/database/backup - Copy the database to a file
+app.post("/backup", async (c) => {
+  try {
+    const { path } = await c.req.json();
+
+    if (!path) {
+      return c.json({ error: "Path is required" }, 400);
+    }
+
+    const destPath = resolve(path);
+    backupDatabase(destPath);
+
+    return c.json({ success: true, path: destPath });
+  } catch (error: any) {
+    console.error("[Database] Failed to back up database:", error.message);
+    return c.json(
+      { error: error.message || "Failed to back up database" },
+      500,
+    );
+  }
+});
+
+// POST /api/database/restore - Replace the database with a backup file
+app.post("/restore", async (c) => {
+  try {
+    const { path } = await c.req.json();
+
+    if (!path) {
+      return c.json({ error: "Path is required" }, 400);
+    }
+
+    const srcPath = resolve(path);
+    if (!existsSync(srcPath)) {
+      return c.json({ error: `File not found: ${srcPath}` }, 404);
+    }
+
+    restoreDatabase(srcPath);
+
+    return c.json({ success: true });
+  } catch (error: any) {
+    console.error("[Database] Failed to restore database:", error.message);
+    return c.json(
+      { error: error.message || "Failed to restore database" },
+      400,
+    );
+  }
+});
+
+// POST /api/database/optimize - Integrity check, VACUUM and PRAGMA optimize
+app.post("/optimize", (c) => {
+  try {
+    return c.json(optimizeDatabase());
+  } catch (error: any) {
+    console.error("[Database] Failed to optimize database:", error.message);
+    return c.json(
+      { error: error.message || "Failed to optimize database" },
+      500,
+    );
+  }
+});
+
+export default app;
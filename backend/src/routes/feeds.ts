@@ -1,11 +1,73 @@
 import { Hono } from "hono";
 import { randomUUID } from "crypto";
 import { getDatabase, resetDatabase, getDatabaseStatus } from "@/db/connection";
-import { fetchFeed } from "@/services/rss";
-import type { Feed } from "@/types";
+import { existsSync } from "fs";
+import { basename, resolve } from "path";
+import { pathToFileURL } from "url";
+import {
+  discoverFeeds,
+  getLocalFeedPath,
+  inspectFeedResponse,
+  parseFeed,
+  previewFeed,
+  readLocalFeed,
+  WebPageError,
+} from "@/services/rss";
+import {
+  REFRESHABLE_FEED_COLUMNS,
+  isHeaderMap,
+  parseFeedHeaders,
+  checkAllFeeds,
+  getConvertOptions,
+  getFeedRequestHeaders,
+  getRefreshConcurrency,
+  getRequestOptions,
+  refreshAllFeeds,
+  refreshFeed,
+  saveArticles,
+  type RefreshableFeed,
+} from "@/services/feedSync";
+import {
+  DEFAULT_TARGET_LANG,
+  getDefaultTargetLang,
+  getTranslationSettings,
+  translateText,
+} from "@/services/translate";
+import { basicAuthorization, encryptSecret } from "@/services/credentials";
+import type {
+  Feed,
+  FeedGroup,
+  FeedPreview,
+  HostStat,
+  UnreadCount,
+} from "@/types";
+import { findDuplicateUrl, normalizeFeedUrl } from "@/utils/url";
 
 const app = new Hono();
 
+// The cached title translation for default_target_lang, while it still
+// matches the feed's current title
+const FEED_COLUMNS = `
+  id, title, (
+    SELECT t.title FROM feed_title_translations t
+    WHERE t.feed_id = feeds.id AND t.source_title = feeds.title
+    AND t.target_lang = COALESCE(
+      (SELECT NULLIF(TRIM(value), '') FROM settings
+       WHERE key = 'default_target_lang'),
+      '${DEFAULT_TARGET_LANG}'
+    )
+  ) as titleTranslated,
+  url, description,
+  image_url as imageUrl, category,
+  prefer_summary as preferSummary, headers,
+  last_error as lastError, last_fetched_at as lastFetchedAt, language,
+  last_fetch_ms as lastFetchMs,
+  refresh_interval_minutes as refreshIntervalMinutes,
+  auto_mark_read as autoMarkRead,
+  sort_order as sortOrder, username, password IS NOT NULL as hasPassword,
+  created_at as createdAt, updated_at as updatedAt
+`;
+
 // GET /api/feeds/status - Check database status and try to reinitialize if needed
 app.get("/status", (c) => {
   const status = getDatabaseStatus();
@@ -37,16 +99,25 @@ app.get("/status", (c) => {
   return c.json(status);
 });
 
+// Feed rows store custom headers as JSON text
+function toFeed(row: any): Feed {
+  return {
+    ...row,
+    headers: parseFeedHeaders(row.headers),
+    hasPassword: row.hasPassword === 1,
+    autoMarkRead: row.autoMarkRead === 1,
+    preferSummary: row.preferSummary === 1,
+  };
+}
+
 // GET /api/feeds - Get all feeds
 app.get("/", (c) => {
   try {
     const db = getDatabase();
-    const query = db.query(`
-      SELECT id, title, url, description, image_url as imageUrl, category, created_at as createdAt, updated_at as updatedAt
-      FROM feeds
-      ORDER BY title
-    `);
-    const feeds = query.all() as Feed[];
+    const query = db.query(
+      `SELECT ${FEED_COLUMNS} FROM feeds ORDER BY sort_order, title`,
+    );
+    const feeds = query.all().map(toFeed);
     return c.json(feeds);
   } catch (error: any) {
     console.error("[Feeds] Failed to get feeds:", error.message);
@@ -54,27 +125,467 @@ app.get("/", (c) => {
   }
 });
 
+const TRUNCATION_SAMPLE_SIZE = 20;
+const TRUNCATION_MIN_ARTICLES = 3;
+const TRUNCATION_RATIO = 0.6;
+
+/**
+ * Heuristic: content ends with an ellipsis or "read more" link, or is very
+ * short relative to its title
+ */
+function looksTruncated(title: string, content: string | null): boolean {
+  const text = (content || "").trim();
+  if (text.length < Math.max(200, title.length * 3)) {
+    return true;
+  }
+
+  const tail = text.slice(-200);
+  return (
+    /(\.\.\.|…)\s*\]?\)?\s*$/.test(tail) ||
+    /read more|continue reading|keep reading|read the rest/i.test(tail)
+  );
+}
+
+// GET /api/feeds/truncated - Feeds whose recent articles look truncated
+app.get("/truncated", (c) => {
+  try {
+    const db = getDatabase();
+    const rows = db
+      .query(
+        `SELECT feed_id as feedId, title, content FROM (
+          SELECT feed_id, title, content,
+            ROW_NUMBER() OVER (PARTITION BY feed_id ORDER BY pub_date DESC) as rn
+          FROM articles
+        ) WHERE rn <= ?`,
+      )
+      .all(TRUNCATION_SAMPLE_SIZE) as {
+      feedId: string;
+      title: string;
+      content: string | null;
+    }[];
+
+    const stats = new Map<string, { total: number; truncated: number }>();
+    for (const row of rows) {
+      const stat = stats.get(row.feedId) || { total: 0, truncated: 0 };
+      stat.total++;
+      if (looksTruncated(row.title, row.content)) {
+        stat.truncated++;
+      }
+      stats.set(row.feedId, stat);
+    }
+
+    const feedIds = [...stats]
+      .filter(
+        ([, stat]) =>
+          stat.total >= TRUNCATION_MIN_ARTICLES &&
+          stat.truncated / stat.total >= TRUNCATION_RATIO,
+      )
+      .map(([feedId]) => feedId);
+
+    return c.json(feedIds);
+  } catch (error: any) {
+    console.error("[Feeds] Failed to detect truncated feeds:", error.message);
+    return c.json(
+      { error: error.message || "Failed to detect truncated feeds" },
+      500,
+    );
+  }
+});
+
+// POST /api/feeds/translate-titles - Translate feed titles for the sidebar
+// into targetLang, default_target_lang when not given
+app.post("/translate-titles", async (c) => {
+  const body = await c.req.json().catch(() => ({}));
+
+  try {
+    const db = getDatabase();
+    const targetLang: string = body.targetLang || getDefaultTargetLang(db);
+    const settings = getTranslationSettings(db);
+    const feeds = db.query("SELECT id, title FROM feeds").all() as {
+      id: string;
+      title: string;
+    }[];
+
+    const cacheQuery = db.query(`
+      SELECT source_title as sourceTitle, title FROM feed_title_translations
+      WHERE feed_id = ? AND target_lang = ?
+    `);
+    const saveCacheQuery = db.query(`
+      INSERT OR REPLACE INTO feed_title_translations
+      (feed_id, target_lang, source_title, title) VALUES (?, ?, ?, ?)
+    `);
+
+    let count = 0;
+    for (const feed of feeds) {
+      const cached = cacheQuery.get(feed.id, targetLang) as {
+        sourceTitle: string;
+        title: string;
+      } | null;
+
+      // Still current for this language
+      if (cached?.sourceTitle === feed.title) {
+        count++;
+        continue;
+      }
+
+      let translated;
+      try {
+        translated = (
+          await translateText(feed.title, targetLang, settings)
+        ).trim();
+      } catch (error: any) {
+        console.error(
+          `[Feeds] Failed to translate title '${feed.title}':`,
+          error.message,
+        );
+        continue;
+      }
+      if (!translated) continue;
+
+      saveCacheQuery.run(feed.id, targetLang, feed.title, translated);
+      count++;
+    }
+
+    return c.json({ count });
+  } catch (error: any) {
+    console.error("[Feeds] Failed to translate feed titles:", error.message);
+    return c.json(
+      { error: error.message || "Failed to translate feed titles" },
+      500,
+    );
+  }
+});
+
+// GET /api/feeds/discover - Find feed URLs advertised by a website
+app.get("/discover", async (c) => {
+  const url = c.req.query("url");
+
+  if (!url) {
+    return c.json({ error: "URL is required" }, 400);
+  }
+
+  try {
+    return c.json(await discoverFeeds(url, getRequestOptions(getDatabase())));
+  } catch (error: any) {
+    console.error("[Feeds] Failed to discover feeds:", error.message);
+    return c.json({ error: error.message || "Failed to discover feeds" }, 400);
+  }
+});
+
+// GET /api/feeds/inspect - Show raw response details for a feed URL
+app.get("/inspect", async (c) => {
+  const url = c.req.query("url");
+
+  if (!url) {
+    return c.json({ error: "URL is required" }, 400);
+  }
+
+  try {
+    const db = getDatabase();
+    // A subscribed feed is requested with its own headers and credentials
+    const feed = db
+      .query(`SELECT ${REFRESHABLE_FEED_COLUMNS} FROM feeds WHERE url = ?`)
+      .get(url) as RefreshableFeed | null;
+
+    return c.json(
+      await inspectFeedResponse(url, {
+        ...getRequestOptions(db),
+        headers: feed ? getFeedRequestHeaders(feed) : undefined,
+      }),
+    );
+  } catch (error: any) {
+    console.error("[Feeds] Failed to inspect feed:", error.message);
+    return c.json({ error: error.message || "Failed to inspect feed" }, 400);
+  }
+});
+
+// GET /api/feeds/preview - Fetch and parse a feed URL without saving it
+app.get("/preview", async (c) => {
+  const url = c.req.query("url");
+
+  if (!url) {
+    return c.json({ error: "URL is required" }, 400);
+  }
+
+  try {
+    return c.json(await previewFeed(url));
+  } catch (error: any) {
+    console.error("[Feeds] Failed to preview feed:", error.message);
+    if (error instanceof WebPageError) {
+      return c.json(await webPageErrorResponse(error, url), 400);
+    }
+    return c.json({ error: error.message || "Failed to preview feed" }, 400);
+  }
+});
+
+// Feeds per host above which refresh-all risks being rate limited
+const HOST_RATE_LIMIT_THRESHOLD = 5;
+
+// GET /api/feeds/health - Fetch every feed and report what is broken
+app.get("/health", async (c) => {
+  try {
+    return c.json(await checkAllFeeds(getDatabase()));
+  } catch (error: any) {
+    console.error("[Feeds] Failed to check feeds:", error.message);
+    return c.json({ error: error.message || "Failed to check feeds" }, 500);
+  }
+});
+
+// GET /api/feeds/slow - Feeds by last fetch duration, slowest first
+app.get("/slow", (c) => {
+  try {
+    const limit = Number(c.req.query("limit")) || 10;
+    const db = getDatabase();
+    const feeds = db
+      .query(
+        `SELECT ${FEED_COLUMNS} FROM feeds WHERE last_fetch_ms IS NOT NULL
+         ORDER BY last_fetch_ms DESC LIMIT ?`,
+      )
+      .all(limit)
+      .map(toFeed);
+    return c.json(feeds);
+  } catch (error: any) {
+    console.error("[Feeds] Failed to get slow feeds:", error.message);
+    return c.json({ error: error.message || "Failed to get slow feeds" }, 500);
+  }
+});
+
+// GET /api/feeds/hosts - Group subscriptions by host
+app.get("/hosts", (c) => {
+  try {
+    const db = getDatabase();
+    const feeds = db.query("SELECT id, url FROM feeds").all() as {
+      id: string;
+      url: string;
+    }[];
+
+    const hosts = new Map<string, string[]>();
+    for (const feed of feeds) {
+      let host: string;
+      try {
+        host = new URL(feed.url).host.toLowerCase();
+      } catch {
+        continue;
+      }
+      hosts.set(host, [...(hosts.get(host) || []), feed.id]);
+    }
+
+    const stats: HostStat[] = [...hosts]
+      .map(([host, feedIds]) => ({
+        host,
+        count: feedIds.length,
+        feedIds,
+        rateLimitRisk: feedIds.length >= HOST_RATE_LIMIT_THRESHOLD,
+      }))
+      .sort((a, b) => b.count - a.count || a.host.localeCompare(b.host));
+
+    return c.json(stats);
+  } catch (error: any) {
+    console.error("[Feeds] Failed to analyze hosts:", error.message);
+    return c.json({ error: error.message || "Failed to analyze hosts" }, 500);
+  }
+});
+
+// GET /api/feeds/unread-counts - Unread article count per feed
+app.get("/unread-counts", (c) => {
+  try {
+    const db = getDatabase();
+    const counts = db
+      .query(
+        `SELECT feed_id as feedId, COUNT(*) as count FROM articles
+         WHERE is_read = 0 GROUP BY feed_id`,
+      )
+      .all() as UnreadCount[];
+
+    return c.json(counts);
+  } catch (error: any) {
+    console.error("[Feeds] Failed to get unread counts:", error.message);
+    return c.json(
+      { error: error.message || "Failed to get unread counts" },
+      500,
+    );
+  }
+});
+
+const UNCATEGORIZED = "Uncategorized";
+
+// GET /api/feeds/grouped - Feeds grouped by category with unread counts
+app.get("/grouped", (c) => {
+  try {
+    const db = getDatabase();
+    const rows = db
+      .query(
+        `SELECT ${FEED_COLUMNS},
+           (SELECT COUNT(*) FROM articles
+            WHERE articles.feed_id = feeds.id AND is_read = 0) as unreadCount
+         FROM feeds ORDER BY sort_order, title`,
+      )
+      .all();
+
+    const groups = new Map<string, FeedGroup>();
+    for (const row of rows) {
+      const feed = toFeed(row) as Feed & { unreadCount: number };
+      const name = feed.category?.trim() || UNCATEGORIZED;
+
+      let group = groups.get(name);
+      if (!group) {
+        group = { name, unreadCount: 0, feeds: [] };
+        groups.set(name, group);
+      }
+      group.feeds.push(feed);
+      group.unreadCount += feed.unreadCount;
+    }
+
+    // Named categories alphabetically, then the uncategorized feeds
+    const grouped = [...groups.values()].sort(
+      (a, b) =>
+        Number(a.name === UNCATEGORIZED) - Number(b.name === UNCATEGORIZED) ||
+        a.name.localeCompare(b.name),
+    );
+
+    return c.json(grouped);
+  } catch (error: any) {
+    console.error("[Feeds] Failed to group feeds:", error.message);
+    return c.json({ error: error.message || "Failed to group feeds" }, 500);
+  }
+});
+
+// POST /api/feeds/reorder - Store the sidebar order of feeds
+app.post("/reorder", async (c) => {
+  try {
+    const { ids } = await c.req.json();
+
+    if (!Array.isArray(ids)) {
+      return c.json({ error: "ids must be an array" }, 400);
+    }
+
+    const db = getDatabase();
+    const update = db.query("UPDATE feeds SET sort_order = ? WHERE id = ?");
+
+    db.transaction((orderedIds: string[]) => {
+      orderedIds.forEach((id, index) => update.run(index, id));
+    })(ids as string[]);
+
+    return c.json({ success: true });
+  } catch (error: any) {
+    console.error("[Feeds] Failed to reorder feeds:", error.message);
+    return c.json({ error: error.message || "Failed to reorder feeds" }, 400);
+  }
+});
+
+const DUPLICATE_FEED_ERROR = "You're already subscribed to this feed";
+
+/**
+ * Error body for a URL that serves a web page, listing the feeds the page
+ * links to so one can be picked instead
+ */
+async function webPageErrorResponse(
+  error: WebPageError,
+  url: string,
+): Promise<{ error: string; candidates: string[] }> {
+  const candidates = await discoverFeeds(
+    url,
+    getRequestOptions(getDatabase()),
+  ).catch(() => []);
+  return { error: error.message, candidates };
+}
+
+/**
+ * Title for a new subscription: the feed's own, else its host name
+ */
+function previewTitle(preview: FeedPreview): string {
+  if (preview.title) {
+    return preview.title;
+  }
+  // Local feeds have no host; their file name will do
+  const localPath = getLocalFeedPath(preview.url);
+  return localPath
+    ? basename(localPath)
+    : new URL(preview.url).hostname.replace(/^www\./, "");
+}
+
 // POST /api/feeds - Add new feed
 app.post("/", async (c) => {
-  const { title, url, description, category } = await c.req.json();
+  const { category, username, password, autoMarkRead, ...body } =
+    await c.req.json();
+  let { title, description } = body;
+  const url = typeof body.url === "string" ? normalizeFeedUrl(body.url) : "";
+
+  if (!url) {
+    return c.json({ error: "URL is required" }, 400);
+  }
 
   try {
     const db = getDatabase();
     const id = randomUUID();
     const now = Math.floor(Date.now() / 1000);
 
+    // Trivial variants (case, trailing slash) count as the same feed; the
+    // existing one is returned so the UI can select it
+    const existing = findDuplicateUrl(
+      url,
+      db.query(`SELECT ${FEED_COLUMNS} FROM feeds`).all() as any[],
+    );
+    if (existing) {
+      return c.json(
+        { error: DUPLICATE_FEED_ERROR, feed: toFeed(existing) },
+        409,
+      );
+    }
+
+    // Without a title, read title and description from the feed itself
+    const autoFill = !title;
+    if (autoFill) {
+      const preview = await previewFeed(
+        url,
+        username
+          ? { Authorization: basicAuthorization(username, password || "") }
+          : {},
+      );
+      title = previewTitle(preview);
+      description ||= preview.description;
+    }
+
     const query = db.query(`
-      INSERT INTO feeds (id, title, url, description, category, created_at, updated_at)
-      VALUES (?, ?, ?, ?, ?, ?, ?)
+      INSERT INTO feeds (id, title, url, description, category, username,
+        password, auto_mark_read, created_at, updated_at)
+      VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
     `);
-    query.run(id, title, url, description || null, category || null, now, now);
+    query.run(
+      id,
+      title,
+      url,
+      description || null,
+      category || null,
+      username || null,
+      username && password ? encryptSecret(password) : null,
+      autoMarkRead ? 1 : 0,
+      now,
+      now,
+    );
+
+    // Paste-and-go: load the first articles right away. A failure here
+    // is recorded in last_error and does not undo the subscription
+    if (autoFill) {
+      const feed = db
+        .query(`SELECT ${REFRESHABLE_FEED_COLUMNS} FROM feeds WHERE id = ?`)
+        .get(id) as RefreshableFeed;
+      try {
+        await refreshFeed(db, feed);
+      } catch (error: any) {
+        console.error("[Feeds] Initial fetch failed:", error.message);
+      }
+    }
 
     const feed: Feed = {
       id,
       title,
       url,
-      description,
+      description: description || undefined,
       category,
+      username: username || null,
+      hasPassword: !!(username && password),
+      autoMarkRead: !!autoMarkRead,
       createdAt: now,
       updatedAt: now,
     };
@@ -82,10 +593,300 @@ app.post("/", async (c) => {
     return c.json(feed, 201);
   } catch (error: any) {
     console.error("[Feeds] Failed to add feed:", error.message);
+    if (error.message?.includes("UNIQUE constraint failed: feeds.url")) {
+      return c.json({ error: DUPLICATE_FEED_ERROR }, 409);
+    }
+    if (error instanceof WebPageError) {
+      return c.json(await webPageErrorResponse(error, url), 400);
+    }
     return c.json({ error: error.message || "Failed to add feed" }, 400);
   }
 });
 
+// POST /api/feeds/import-file - Subscribe to a feed read from a local file
+app.post("/import-file", async (c) => {
+  const { path, title, category } = await c.req.json();
+
+  if (!path || !title) {
+    return c.json({ error: "Path and title are required" }, 400);
+  }
+
+  const filePath = resolve(path);
+  if (!existsSync(filePath)) {
+    return c.json({ error: `File not found: ${filePath}` }, 404);
+  }
+
+  try {
+    const db = getDatabase();
+    const url = pathToFileURL(filePath).href;
+    const existing = findDuplicateUrl(
+      url,
+      db.query(`SELECT ${FEED_COLUMNS} FROM feeds`).all() as any[],
+    );
+    if (existing) {
+      return c.json(
+        { error: DUPLICATE_FEED_ERROR, feed: toFeed(existing) },
+        409,
+      );
+    }
+
+    let body;
+    try {
+      body = await readLocalFeed(filePath);
+    } catch (error: any) {
+      return c.json({ error: error.message }, 400);
+    }
+
+    let articles;
+    try {
+      // The file URL is the base when the feed doesn't link its site
+      articles = await parseFeed(body, getConvertOptions(db), url);
+    } catch (error: any) {
+      return c.json(
+        { error: `Failed to parse feed file: ${error.message}` },
+        400,
+      );
+    }
+
+    const id = randomUUID();
+    const now = Math.floor(Date.now() / 1000);
+
+    db.query(
+      `INSERT INTO feeds (id, title, url, category, created_at, updated_at)
+       VALUES (?, ?, ?, ?, ?, ?)`,
+    ).run(id, title, url, category || null, now, now);
+
+    const count = saveArticles(db, id, articles).saved.length;
+
+    const feed: Feed = {
+      id,
+      title,
+      url,
+      category,
+      createdAt: now,
+      updatedAt: now,
+    };
+
+    return c.json({ feed, count }, 201);
+  } catch (error: any) {
+    console.error("[Feeds] Failed to import feed file:", error.message);
+    return c.json({ error: error.message || "Failed to import feed" }, 400);
+  }
+});
+
+// POST /api/feeds/import-urls - Subscribe to a newline-separated URL list
+// Blank lines and # comments are ignored; subscribed URLs are skipped
+app.post("/import-urls", async (c) => {
+  const { text, category } = await c.req.json();
+
+  if (typeof text !== "string") {
+    return c.json({ error: "Text is required" }, 400);
+  }
+
+  const lines = text
+    .split(/\r?\n/)
+    .map((line) => line.trim())
+    .filter((line) => line && !line.startsWith("#"));
+  // One entry per feed, even if the list repeats it with a trailing slash
+  const urls = [
+    ...new Map(lines.map((url) => [normalizeFeedUrl(url), url])).values(),
+  ];
+
+  try {
+    const db = getDatabase();
+    const subscribed = db.query("SELECT url FROM feeds").all() as {
+      url: string;
+    }[];
+    const pending = urls.filter((url) => !findDuplicateUrl(url, subscribed));
+    const insertQuery = db.query(`
+      INSERT OR IGNORE INTO feeds
+      (id, title, url, description, category, created_at, updated_at)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `);
+
+    const created = new Map<string, Feed>();
+    let next = 0;
+
+    // Titles come from fetching each feed, a few at a time
+    const worker = async () => {
+      while (next < pending.length) {
+        const url = normalizeFeedUrl(pending[next++]);
+        try {
+          const preview = await previewFeed(url);
+          const id = randomUUID();
+          const now = Math.floor(Date.now() / 1000);
+          const title = previewTitle(preview);
+          const description = preview.description || undefined;
+
+          const result = insertQuery.run(
+            id,
+            title,
+            url,
+            description || null,
+            category || null,
+            now,
+            now,
+          );
+          if (result.changes > 0) {
+            created.set(url, {
+              id,
+              title,
+              url,
+              description,
+              category,
+              createdAt: now,
+              updatedAt: now,
+            });
+          }
+        } catch (error: any) {
+          console.error(`[Feeds] Skipping ${url}: ${error.message}`);
+        }
+      }
+    };
+
+    await Promise.all(
+      Array.from(
+        { length: Math.min(getRefreshConcurrency(db), pending.length) },
+        worker,
+      ),
+    );
+
+    // Same order as the list
+    const feeds = pending.flatMap(
+      (url) => created.get(normalizeFeedUrl(url)) ?? [],
+    );
+    return c.json(feeds, 201);
+  } catch (error: any) {
+    console.error("[Feeds] Failed to import URL list:", error.message);
+    return c.json({ error: error.message || "Failed to import URLs" }, 400);
+  }
+});
+
+// PATCH /api/feeds/:id - Update feed details and settings
+// Only the provided fields change; articles stay attached to the feed id
+app.patch("/:id", async (c) => {
+  const { id } = c.req.param();
+  const {
+    title,
+    url,
+    description,
+    category,
+    preferSummary,
+    headers,
+    refreshIntervalMinutes,
+    username,
+    password,
+    autoMarkRead,
+  } = await c.req.json();
+
+  if (title !== undefined && !String(title).trim()) {
+    return c.json({ error: "Title cannot be empty" }, 400);
+  }
+  if (url !== undefined && !String(url).trim()) {
+    return c.json({ error: "URL cannot be empty" }, 400);
+  }
+  if (headers != null && !isHeaderMap(headers)) {
+    return c.json({ error: "headers must be an object of strings" }, 400);
+  }
+  if (
+    refreshIntervalMinutes != null &&
+    !(Number.isInteger(refreshIntervalMinutes) && refreshIntervalMinutes > 0)
+  ) {
+    return c.json(
+      { error: "refreshIntervalMinutes must be a positive integer" },
+      400,
+    );
+  }
+
+  try {
+    const db = getDatabase();
+
+    const updates: string[] = [];
+    const params: any[] = [];
+
+    if (title !== undefined) {
+      updates.push("title = ?");
+      params.push(String(title).trim());
+    }
+
+    // Cache validators belong to the old URL
+    if (url !== undefined) {
+      updates.push("url = ?", "etag = NULL", "last_modified = NULL");
+      params.push(normalizeFeedUrl(String(url)));
+    }
+
+    if (description !== undefined) {
+      updates.push("description = ?");
+      params.push(description || null);
+    }
+
+    if (category !== undefined) {
+      updates.push("category = ?");
+      params.push(category || null);
+    }
+
+    if (preferSummary !== undefined) {
+      updates.push("prefer_summary = ?");
+      params.push(preferSummary ? 1 : 0);
+    }
+
+    if (autoMarkRead !== undefined) {
+      updates.push("auto_mark_read = ?");
+      params.push(autoMarkRead ? 1 : 0);
+    }
+
+    // null clears the custom headers
+    if (headers !== undefined) {
+      updates.push("headers = ?");
+      params.push(headers ? JSON.stringify(headers) : null);
+    }
+
+    // null falls back to the global auto-refresh interval
+    if (refreshIntervalMinutes !== undefined) {
+      updates.push("refresh_interval_minutes = ?");
+      params.push(refreshIntervalMinutes);
+    }
+
+    // Removing the user also drops its password
+    if (username !== undefined) {
+      updates.push("username = ?");
+      params.push(username || null);
+      if (!username) {
+        updates.push("password = NULL");
+      }
+    }
+
+    if (password !== undefined && (username === undefined || username)) {
+      updates.push("password = ?");
+      params.push(password ? encryptSecret(password) : null);
+    }
+
+    if (updates.length > 0) {
+      updates.push("updated_at = ?");
+      params.push(Math.floor(Date.now() / 1000), id);
+      db.query(`UPDATE feeds SET ${updates.join(", ")} WHERE id = ?`).run(
+        ...params,
+      );
+    }
+
+    const row = db
+      .query(`SELECT ${FEED_COLUMNS} FROM feeds WHERE id = ?`)
+      .get(id);
+
+    if (!row) {
+      return c.json({ error: "Feed not found" }, 404);
+    }
+
+    return c.json(toFeed(row));
+  } catch (error: any) {
+    console.error("[Feeds] Failed to update feed:", error.message);
+    if (error.message?.includes("UNIQUE constraint failed: feeds.url")) {
+      return c.json({ error: "Feed already exists" }, 409);
+    }
+    return c.json({ error: error.message || "Failed to update feed" }, 400);
+  }
+});
+
 // DELETE /api/feeds/:id - Delete feed
 app.delete("/:id", (c) => {
   const { id } = c.req.param();
@@ -93,8 +894,9 @@ app.delete("/:id", (c) => {
   try {
     const db = getDatabase();
 
-    // Delete articles first
+    // Delete articles and feed-specific filter rules first
     db.query("DELETE FROM articles WHERE feed_id = ?").run(id);
+    db.query("DELETE FROM filter_rules WHERE feed_id = ?").run(id);
 
     // Delete feed
     const result = db.query("DELETE FROM feeds WHERE id = ?").run(id);
@@ -110,6 +912,29 @@ app.delete("/:id", (c) => {
   }
 });
 
+// DELETE /api/feeds/:id/translations - Clear cached translations for a feed
+app.delete("/:id/translations", (c) => {
+  const { id } = c.req.param();
+
+  try {
+    const db = getDatabase();
+    const result = db
+      .query(
+        `DELETE FROM translations
+         WHERE article_id IN (SELECT id FROM articles WHERE feed_id = ?)`,
+      )
+      .run(id);
+
+    return c.json({ count: result.changes });
+  } catch (error: any) {
+    console.error("[Feeds] Failed to clear feed translations:", error.message);
+    return c.json(
+      { error: error.message || "Failed to clear translations" },
+      400,
+    );
+  }
+});
+
 // POST /api/feeds/:id/refresh - Refresh specific feed
 app.post("/:id/refresh", async (c) => {
   const { id } = c.req.param();
@@ -118,60 +943,28 @@ app.post("/:id/refresh", async (c) => {
     const db = getDatabase();
 
     // Get feed info
-    const query = db.query("SELECT url, title FROM feeds WHERE id = ?");
-    const feed = query.get(id) as { url: string; title: string } | null;
+    const query = db.query(
+      `SELECT ${REFRESHABLE_FEED_COLUMNS} FROM feeds WHERE id = ?`,
+    );
+    const feed = query.get(id) as RefreshableFeed | null;
 
     if (!feed) {
       return c.json({ error: "Feed not found" }, 404);
     }
 
-    // Fetch articles with 5s timeout
-    const timeoutPromise = new Promise<never>((_, reject) => {
-      setTimeout(() => reject(new Error("Timeout")), 5000);
-    });
-
-    const articles = await Promise.race([fetchFeed(feed.url), timeoutPromise]);
-    const now = Math.floor(Date.now() / 1000);
-
-    // Get existing article links
-    const linksQuery = db.query("SELECT link FROM articles WHERE feed_id = ?");
-    const existingLinks = new Set(
-      (linksQuery.all(id) as { link: string }[]).map((row) => row.link),
+    // Fetch articles, bounded by the fetch_timeout_secs setting
+    const { count, total, skipped, newIds, updated } = await refreshFeed(
+      db,
+      feed,
     );
 
-    let savedCount = 0;
-    const insertQuery = db.query(`
-      INSERT OR IGNORE INTO articles 
-      (id, feed_id, title, link, content, summary, author, pub_date, is_read, is_starred, fetched_at)
-      VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?)
-    `);
-
-    for (const article of articles) {
-      if (existingLinks.has(article.link)) {
-        continue;
-      }
-
-      insertQuery.run(
-        article.id,
-        id,
-        article.title,
-        article.link,
-        article.content,
-        article.summary || null,
-        article.author || null,
-        article.pubDate || now,
-        now,
-      );
-
-      savedCount++;
-    }
-
-    db.query("UPDATE feeds SET updated_at = ? WHERE id = ?").run(now, id);
-
     return c.json({
       success: true,
-      count: savedCount,
-      total: articles.length,
+      count,
+      total,
+      skipped,
+      newIds,
+      updated,
       title: feed.title,
     });
   } catch (error: any) {
@@ -191,115 +984,24 @@ app.post("/:id/refresh", async (c) => {
   }
 });
 
-// Helper function to refresh a single feed with timeout
-async function refreshSingleFeed(
-  feedId: string,
-  url: string,
-  title: string,
-  db: any,
-): Promise<{ success: boolean; count: number; error?: string }> {
-  try {
-    const timeoutPromise = new Promise<never>((_, reject) => {
-      setTimeout(() => reject(new Error("Feed refresh timeout (5s)")), 5000);
-    });
-
-    const articles = await Promise.race([fetchFeed(url), timeoutPromise]);
-    const now = Math.floor(Date.now() / 1000);
-
-    const linksQuery = db.query("SELECT link FROM articles WHERE feed_id = ?");
-    const existingLinks = new Set(
-      (linksQuery.all(feedId) as { link: string }[]).map((row) => row.link),
-    );
-
-    let savedCount = 0;
-    const insertQuery = db.query(`
-      INSERT OR IGNORE INTO articles 
-      (id, feed_id, title, link, content, summary, author, pub_date, is_read, is_starred, fetched_at)
-      VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?)
-    `);
-
-    for (const article of articles) {
-      if (existingLinks.has(article.link)) {
-        continue;
-      }
-
-      insertQuery.run(
-        article.id,
-        feedId,
-        article.title,
-        article.link,
-        article.content,
-        article.summary || null,
-        article.author || null,
-        article.pubDate || now,
-        now,
-      );
-
-      savedCount++;
-    }
-
-    db.query("UPDATE feeds SET updated_at = ? WHERE id = ?").run(now, feedId);
-
-    return { success: true, count: savedCount };
-  } catch (error: any) {
-    const errorMsg = error.message || "Unknown error";
-    console.error(`[Feeds] Failed to refresh feed '${title}': ${errorMsg}`);
-    return { success: false, count: 0, error: `${title}: ${errorMsg}` };
-  }
-}
-
 // POST /api/feeds/refresh-all - Refresh all feeds
 app.post("/refresh-all", async (c) => {
   console.log("[Feeds] Starting refresh-all...");
 
   try {
     const db = getDatabase();
-    const feedsQuery = db.query("SELECT id, url, title FROM feeds");
-    const feeds = feedsQuery.all() as {
-      id: string;
-      url: string;
-      title: string;
-    }[];
-
-    console.log(`[Feeds] Found ${feeds.length} feeds to refresh`);
-
-    // Process feeds with concurrency limit (5 at a time) for speed
-    const CONCURRENCY = 5;
-    let totalCount = 0;
-    const errors: string[] = [];
-
-    for (let i = 0; i < feeds.length; i += CONCURRENCY) {
-      const batch = feeds.slice(i, i + CONCURRENCY);
-      console.log(
-        `[Feeds] Processing batch ${Math.floor(i / CONCURRENCY) + 1}/${Math.ceil(feeds.length / CONCURRENCY)}`,
-      );
-
-      const results = await Promise.all(
-        batch.map((feed) =>
-          refreshSingleFeed(feed.id, feed.url, feed.title, db),
-        ),
-      );
-
-      for (const result of results) {
-        if (result.success) {
-          totalCount += result.count;
-        } else {
-          errors.push(result.error!);
-        }
-      }
-    }
-
-    console.log(
-      `[Feeds] Refresh-all complete: ${totalCount} new articles, ${errors.length} errors`,
-    );
+    const { count, newIds, errors, totalFeeds, results } =
+      await refreshAllFeeds(db);
 
     // Always return 200 with results, even if some feeds failed
     return c.json({
-      count: totalCount,
+      count,
+      newIds,
       errors: errors.length > 0 ? errors : undefined,
-      totalFeeds: feeds.length,
-      successCount: feeds.length - errors.length,
+      totalFeeds,
+      successCount: totalFeeds - errors.length,
       failedCount: errors.length,
+      results,
     });
   } catch (error: any) {
     console.error("[Feeds] Critical error in refresh-all:", error.message);
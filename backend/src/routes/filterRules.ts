@@ -0,0 +1,150 @@
+import { Hono } from "hono";
+import { randomUUID } from "crypto";
+import { getDatabase } from "@/db/connection";
+import { FILTER_RULE_ACTIONS, FILTER_RULE_FIELDS } from "@/utils/filterRules";
+import type { FilterRule, FilterRuleAction, FilterRuleField } from "@/types";
+
+const app = new Hono();
+
+const FILTER_RULE_COLUMNS = `id, pattern, field, action, feed_id as feedId,
+  created_at as createdAt`;
+
+/**
+ * Validate a rule body, returning an error message for bad input
+ */
+function validateRule(body: any): string | null {
+  if (typeof body.pattern !== "string" || !body.pattern.trim()) {
+    return "Pattern is required";
+  }
+  if (!FILTER_RULE_FIELDS.includes(body.field)) {
+    return `Field must be one of: ${FILTER_RULE_FIELDS.join(", ")}`;
+  }
+  if (!FILTER_RULE_ACTIONS.includes(body.action)) {
+    return `Action must be one of: ${FILTER_RULE_ACTIONS.join(", ")}`;
+  }
+  if (body.feedId != null && typeof body.feedId !== "string") {
+    return "Invalid feedId";
+  }
+  return null;
+}
+
+// GET /api/filter-rules - List rules, optionally only those for one feed
+app.get("/", (c) => {
+  const feedId = c.req.query("feedId");
+
+  try {
+    const db = getDatabase();
+    const rules = feedId
+      ? db
+          .query(
+            `SELECT ${FILTER_RULE_COLUMNS} FROM filter_rules
+             WHERE feed_id = ? ORDER BY created_at`,
+          )
+          .all(feedId)
+      : db
+          .query(
+            `SELECT ${FILTER_RULE_COLUMNS} FROM filter_rules
+             ORDER BY created_at`,
+          )
+          .all();
+
+    return c.json(rules as FilterRule[]);
+  } catch (error: any) {
+    console.error("[FilterRules] Failed to list rules:", error.message);
+    return c.json({ error: error.message || "Failed to list rules" }, 500);
+  }
+});
+
+// POST /api/filter-rules - Create a rule
+app.post("/", async (c) => {
+  try {
+    const body = await c.req.json();
+    const invalid = validateRule(body);
+    if (invalid) {
+      return c.json({ error: invalid }, 400);
+    }
+
+    const db = getDatabase();
+    const id = randomUUID();
+    db.query(
+      `INSERT INTO filter_rules (id, pattern, field, action, feed_id)
+       VALUES (?, ?, ?, ?, ?)`,
+    ).run(
+      id,
+      body.pattern.trim(),
+      body.field as FilterRuleField,
+      body.action as FilterRuleAction,
+      body.feedId || null,
+    );
+
+    const rule = db
+      .query(`SELECT ${FILTER_RULE_COLUMNS} FROM filter_rules WHERE id = ?`)
+      .get(id) as FilterRule;
+
+    return c.json(rule, 201);
+  } catch (error: any) {
+    console.error("[FilterRules] Failed to create rule:", error.message);
+    return c.json({ error: error.message || "Failed to create rule" }, 400);
+  }
+});
+
+// PUT /api/filter-rules/:id - Replace a rule
+app.put("/:id", async (c) => {
+  const { id } = c.req.param();
+
+  try {
+    const body = await c.req.json();
+    const invalid = validateRule(body);
+    if (invalid) {
+      return c.json({ error: invalid }, 400);
+    }
+
+    const db = getDatabase();
+    const result = db
+      .query(
+        `UPDATE filter_rules SET pattern = ?, field = ?, action = ?,
+         feed_id = ? WHERE id = ?`,
+      )
+      .run(
+        body.pattern.trim(),
+        body.field as FilterRuleField,
+        body.action as FilterRuleAction,
+        body.feedId || null,
+        id,
+      );
+
+    if (result.changes === 0) {
+      return c.json({ error: "Rule not found" }, 404);
+    }
+
+    const rule = db
+      .query(`SELECT ${FILTER_RULE_COLUMNS} FROM filter_rules WHERE id = ?`)
+      .get(id) as FilterRule;
+
+    return c.json(rule);
+  } catch (error: any) {
+    console.error("[FilterRules] Failed to update rule:", error.message);
+    return c.json({ error: error.message || "Failed to update rule" }, 400);
+  }
+});
+
+// DELETE /api/filter-rules/:id - Delete a rule
+app.delete("/:id", (c) => {
+  const { id } = c.req.param();
+
+  try {
+    const db = getDatabase();
+    const result = db.query("DELETE FROM filter_rules WHERE id = ?").run(id);
+
+    if (result.changes === 0) {
+      return c.json({ error: "Rule not found" }, 404);
+    }
+
+    return c.json({ success: true });
+  } catch (error: any) {
+    console.error("[FilterRules] Failed to delete rule:", error.message);
+    return c.json({ error: error.message || "Failed to delete rule" }, 400);
+  }
+});
+
+export default app;
@@ -3,6 +3,67 @@ import { getDatabase } from "@/db/connection";
 
 const app = new Hono();
 
+// Settings left out of exports made for sharing
+const SECRET_SETTING = /(api_key|token|secret|password)$/;
+
+// GET /api/settings/export - All settings as one JSON object
+// ?excludeSecrets=true leaves out API keys and other credentials
+app.get("/export", (c) => {
+  const excludeSecrets = c.req.query("excludeSecrets") === "true";
+  const db = getDatabase();
+
+  try {
+    const rows = db
+      .query("SELECT key, value FROM settings ORDER BY key")
+      .all() as { key: string; value: string }[];
+
+    const settings: Record<string, string> = {};
+    for (const { key, value } of rows) {
+      if (!excludeSecrets || !SECRET_SETTING.test(key)) {
+        settings[key] = value;
+      }
+    }
+
+    return c.json(settings);
+  } catch (error: any) {
+    return c.json({ error: error.message || "Failed to export settings" }, 500);
+  }
+});
+
+// POST /api/settings/import - Save every setting in a JSON object, as made
+// by /export. Settings missing from the object are left unchanged
+app.post("/import", async (c) => {
+  const settings = await c.req.json();
+
+  if (!settings || typeof settings !== "object" || Array.isArray(settings)) {
+    return c.json({ error: "Expected a JSON object of settings" }, 400);
+  }
+
+  const entries = Object.entries(settings).filter(
+    ([, value]) => value !== null && value !== undefined,
+  );
+  if (entries.some(([, value]) => typeof value === "object")) {
+    return c.json({ error: "Setting values must be strings" }, 400);
+  }
+
+  const db = getDatabase();
+
+  try {
+    const query = db.query(
+      "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+    );
+    db.transaction(() => {
+      for (const [key, value] of entries) {
+        query.run(key, String(value));
+      }
+    })();
+
+    return c.json({ count: entries.length });
+  } catch (error: any) {
+    return c.json({ error: error.message || "Failed to import settings" }, 400);
+  }
+});
+
 // GET /api/settings/:key - Get setting value
 app.get("/:key", (c) => {
   const { key } = c.req.param();
@@ -0,0 +1,32 @@
+import { Hono } from "hono";
+import { getDatabase } from "@/db/connection";
+import type { Stats } from "@/types";
+
+const app = new Hono();
+
+// GET /api/stats - Totals for the stats panel
+app.get("/", (c) => {
+  try {
+    const db = getDatabase();
+    const since = Math.floor(Date.now() / 1000) - 24 * 60 * 60;
+
+    const stats = db
+      .query(
+        `SELECT
+           (SELECT COUNT(*) FROM feeds) as totalFeeds,
+           COUNT(*) as totalArticles,
+           COALESCE(SUM(is_read = 0), 0) as unreadCount,
+           COALESCE(SUM(is_starred = 1), 0) as starredCount,
+           COALESCE(SUM(fetched_at >= ?), 0) as fetchedLast24h
+         FROM articles`,
+      )
+      .get(since) as Stats;
+
+    return c.json(stats);
+  } catch (error: any) {
+    console.error("[Stats] Failed to get stats:", error.message);
+    return c.json({ error: error.message || "Failed to get stats" }, 500);
+  }
+});
+
+export default app;
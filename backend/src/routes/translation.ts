@@ -1,7 +1,17 @@
 import { Hono } from "hono";
+import { stream } from "hono/streaming";
 import { getDatabase } from "@/db/connection";
-import { translateText } from "@/services/translate";
+import {
+  getDefaultTargetLang,
+  getTranslationConfigSummary,
+  getTranslationSettings,
+  translateTextCached,
+  translateTextStreamCached,
+  translateTextsCached,
+} from "@/services/translate";
+import { chunk, placeholders } from "@/utils/sql";
 import { htmlToMarkdown } from "@/utils/htmlToMarkdown";
+import { sanitizeHtml } from "@/utils/sanitize";
 
 const app = new Hono();
 
@@ -20,9 +30,10 @@ function isHtmlContent(text: string): boolean {
 function ensureMarkdown(content: string): string {
   if (!content) return content;
 
-  // If content contains HTML tags, convert it
+  // If content contains HTML tags (e.g. rows stored before conversion),
+  // sanitize and convert it
   if (isHtmlContent(content)) {
-    return htmlToMarkdown(content);
+    return htmlToMarkdown(sanitizeHtml(content));
   }
 
   // Already Markdown (or plain text)
@@ -40,27 +51,14 @@ app.post("/", async (c) => {
   const db = getDatabase();
 
   try {
-    // Get translation settings
-    const getSettingValue = (key: string, defaultValue: string): string => {
-      const query = db.query("SELECT value FROM settings WHERE key = ?");
-      const result = query.get(key) as { value: string } | null;
-      return result?.value || defaultValue;
-    };
-
-    const settings = {
-      baseUrl: getSettingValue(
-        "translation_base_url",
-        "https://libretranslate.com",
-      ),
-      apiKey: getSettingValue("translation_api_key", ""),
-      model: getSettingValue("translation_model", "gpt-3.5-turbo"),
-      prompt: getSettingValue(
-        "translation_prompt",
-        "Translate the following text to Chinese:",
-      ),
-    };
-
-    const translated = await translateText(text, targetLang || "zh", settings);
+    const settings = getTranslationSettings(db);
+
+    const translated = await translateTextCached(
+      db,
+      text,
+      targetLang || getDefaultTargetLang(db),
+      settings,
+    );
 
     return c.json({ translatedText: translated });
   } catch (error: any) {
@@ -68,9 +66,107 @@ app.post("/", async (c) => {
   }
 });
 
+// POST /api/translate/stream - Translate text, sending the translation as
+// it is produced: one JSON object per line, {"text"} for each piece or
+// {"error"} if the provider fails partway
+app.post("/stream", async (c) => {
+  const { text, targetLang } = await c.req.json();
+
+  if (!text) {
+    return c.json({ error: "Text is required" }, 400);
+  }
+
+  const db = getDatabase();
+  const pieces = translateTextStreamCached(
+    db,
+    text,
+    targetLang || getDefaultTargetLang(db),
+    getTranslationSettings(db),
+  );
+
+  // Wait for the first piece so failing requests still get an error status
+  let first: IteratorResult<string>;
+  try {
+    first = await pieces.next();
+  } catch (error: any) {
+    return c.json({ error: error.message || "Translation failed" }, 400);
+  }
+
+  c.header("Content-Type", "application/x-ndjson");
+  return stream(c, async (output) => {
+    try {
+      if (!first.done) {
+        await output.writeln(JSON.stringify({ text: first.value }));
+      }
+      for await (const piece of pieces) {
+        await output.writeln(JSON.stringify({ text: piece }));
+      }
+    } catch (error: any) {
+      console.error("[Translate] Streaming failed:", error.message);
+      await output.writeln(
+        JSON.stringify({ error: error.message || "Translation failed" }),
+      );
+    }
+  });
+});
+
+// POST /api/translate/articles - Translate the titles of many articles
+app.post("/articles", async (c) => {
+  const { ids, targetLang } = await c.req.json();
+
+  if (!Array.isArray(ids)) {
+    return c.json({ error: "ids must be an array" }, 400);
+  }
+
+  const db = getDatabase();
+
+  try {
+    const titles = new Map<string, string>();
+    for (const batch of chunk(ids as string[])) {
+      const rows = db
+        .query(
+          `SELECT id, title FROM articles
+           WHERE id IN (${placeholders(batch.length)})`,
+        )
+        .all(...batch) as { id: string; title: string }[];
+      rows.forEach((row) => titles.set(row.id, row.title));
+    }
+
+    const found = [...titles.keys()];
+    const translated = await translateTextsCached(
+      db,
+      found.map((id) => titles.get(id)!),
+      targetLang || getDefaultTargetLang(db),
+      getTranslationSettings(db),
+    );
+
+    return c.json(found.map((id, i) => ({ id, title: translated[i] })));
+  } catch (error: any) {
+    return c.json(
+      { error: error.message || "Failed to translate articles" },
+      400,
+    );
+  }
+});
+
+// GET /api/translate/config-summary - Effective provider, model and host
+app.get("/config-summary", (c) => {
+  const db = getDatabase();
+
+  try {
+    return c.json(getTranslationConfigSummary(getTranslationSettings(db)));
+  } catch (error: any) {
+    return c.json(
+      { error: error.message || "Failed to get translation config" },
+      400,
+    );
+  }
+});
+
 // POST /api/translations - Save translation for article
+// Each target language is kept separately, default_target_lang if not given
 app.post("/save", async (c) => {
-  const { articleId, content } = await c.req.json();
+  const { articleId, content, targetLang } = await c.req.json();
 
   if (!articleId || !content) {
     return c.json({ error: "Article ID and content are required" }, 400);
@@ -81,9 +177,10 @@ app.post("/save", async (c) => {
 
   try {
     const query = db.query(
-      "INSERT OR REPLACE INTO translations (article_id, content, created_at) VALUES (?, ?, ?)",
+      `INSERT OR REPLACE INTO translations
+       (article_id, target_lang, content, created_at) VALUES (?, ?, ?, ?)`,
     );
-    query.run(articleId, content, now);
+    query.run(articleId, targetLang || getDefaultTargetLang(db), content, now);
 
     return c.json({ success: true });
   } catch (error: any) {
@@ -95,15 +192,20 @@ app.post("/save", async (c) => {
 });
 
 // GET /api/translations/:articleId - Get translation for article
+// ?targetLang= picks the language, default_target_lang if not given
 app.get("/:articleId", (c) => {
   const { articleId } = c.req.param();
   const db = getDatabase();
+  const targetLang = c.req.query("targetLang") || getDefaultTargetLang(db);
 
   try {
     const query = db.query(
-      "SELECT content FROM translations WHERE article_id = ?",
+      `SELECT content FROM translations
+       WHERE article_id = ? AND target_lang = ?`,
     );
-    const result = query.get(articleId) as { content: string } | null;
+    const result = query.get(articleId, targetLang) as {
+      content: string;
+    } | null;
 
     // Convert HTML to Markdown if needed for legacy data
     const content = result?.content ? ensureMarkdown(result.content) : null;
@@ -0,0 +1,64 @@
+import type { Database } from "bun:sqlite";
+import { getListSetting, getSetting } from "@/db/settings";
+import type { FeedArticle } from "@/services/rss";
+import {
+  getTranslationSettings,
+  translateTextsCached,
+} from "@/services/translate";
+
+/**
+ * Target language for new articles in a feed's language, from the
+ * auto_translate_from and auto_translate_to settings
+ * @returns null when the language is not listed or no target is set
+ */
+export function getAutoTranslateTarget(
+  db: Database,
+  language: string | null,
+): string | null {
+  const target = getSetting(db, "auto_translate_to")?.trim();
+  if (!target || !language) {
+    return null;
+  }
+
+  // Compare primary subtags so "en" also covers "en-US"
+  const primary = (tag: string) => tag.toLowerCase().split(/[-_]/)[0];
+  const sources = getListSetting(db, "auto_translate_from").map(primary);
+
+  return sources.includes(primary(language)) ? target : null;
+}
+
+/**
+ * Translate the title and summary of newly saved articles ahead of time.
+ * The results go to the translation cache, so translating them later is
+ * instant, and not to translations: that table holds the full-article
+ * translations the user asked for. Texts already cached in targetLang are
+ * not sent again
+ */
+export async function autoTranslateArticles(
+  db: Database,
+  articles: FeedArticle[],
+  targetLang: string,
+): Promise<void> {
+  if (articles.length === 0) {
+    return;
+  }
+
+  const settings = getTranslationSettings(db);
+  await translateTextsCached(
+    db,
+    articles.map((article) => article.title),
+    targetLang,
+    settings,
+  );
+  // Summary-less articles only get their title translated
+  await translateTextsCached(
+    db,
+    articles
+      .filter((article) => article.summary?.trim())
+      .map((article) => article.summary!),
+    targetLang,
+    settings,
+  );
+
+  console.log(`[Translate] Auto-translated ${articles.length} articles`);
+}
@@ -0,0 +1,68 @@
+import { createCipheriv, createDecipheriv, randomBytes } from "crypto";
+import { existsSync, readFileSync, writeFileSync } from "fs";
+import { join } from "path";
+import { getDbDir } from "@/db/connection";
+
+// Kept next to data.db but outside it, so a copied database alone does not
+// reveal stored passwords
+const KEY_FILE = "secret.key";
+const ALGORITHM = "aes-256-gcm";
+
+let key: Buffer | null = null;
+
+/**
+ * Load the encryption key, creating it on first use
+ */
+function getKey(): Buffer {
+  if (key) return key;
+
+  const keyPath = join(getDbDir(), KEY_FILE);
+  if (existsSync(keyPath)) {
+    key = Buffer.from(readFileSync(keyPath, "utf-8").trim(), "base64");
+  } else {
+    key = randomBytes(32);
+    writeFileSync(keyPath, key.toString("base64"), { mode: 0o600 });
+  }
+  return key;
+}
+
+/**
+ * Encrypt a secret for storage as "iv.tag.ciphertext" (base64 parts)
+ */
+export function encryptSecret(plain: string): string {
+  const iv = randomBytes(12);
+  const cipher = createCipheriv(ALGORITHM, getKey(), iv);
+  const data = Buffer.concat([cipher.update(plain, "utf-8"), cipher.final()]);
+
+  return [iv, cipher.getAuthTag(), data]
+    .map((part) => part.toString("base64"))
+    .join(".");
+}
+
+/**
+ * Decrypt a value from encryptSecret
+ * Throws if it was encrypted with another key, e.g. a database restored on
+ * a different machine
+ */
+export function decryptSecret(stored: string): string {
+  const [iv, tag, data] = stored
+    .split(".")
+    .map((part) => Buffer.from(part, "base64"));
+
+  try {
+    const decipher = createDecipheriv(ALGORITHM, getKey(), iv);
+    decipher.setAuthTag(tag);
+    return Buffer.concat([decipher.update(data), decipher.final()]).toString(
+      "utf-8",
+    );
+  } catch {
+    throw new Error("Stored password cannot be decrypted, please re-enter it");
+  }
+}
+
+/**
+ * Authorization header value for HTTP basic auth
+ */
+export function basicAuthorization(username: string, password: string): string {
+  return `Basic ${Buffer.from(`${username}:${password}`).toString("base64")}`;
+}
@@ -0,0 +1,8 @@
+/**
+ * Notify the Tauri shell of a backend event. The shell reads the sidecar's
+ * stdout and re-emits `EVENT:<name>:<json>` lines as Tauri events, the same
+ * channel used to report the PORT on startup.
+ */
+export function emitEvent(name: string, payload: unknown): void {
+  console.log(`EVENT:${name}:${JSON.stringify(payload)}`);
+}
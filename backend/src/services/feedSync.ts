@@ -0,0 +1,714 @@
+import type { Database } from "bun:sqlite";
+import { getBoolSetting, getIntSetting, getSetting } from "@/db/settings";
+import { emitEvent } from "@/services/events";
+import {
+  beginNotificationBatch,
+  queueNewArticlesNotification,
+} from "@/services/notifications";
+import {
+  DEFAULT_SUMMARY_CHAR_LIMIT,
+  DEFAULT_SUMMARY_WORD_LIMIT,
+  RateLimitedError,
+  checkFeedHealth,
+  fetchFeed,
+  type ConvertOptions,
+  type FeedArticle,
+  type RequestOptions,
+} from "@/services/rss";
+import { basicAuthorization, decryptSecret } from "@/services/credentials";
+import {
+  autoTranslateArticles,
+  getAutoTranslateTarget,
+} from "@/services/autoTranslate";
+import type { FeedHealth, FilterRule, RefreshResult } from "@/types";
+import { evaluateFilterRules } from "@/utils/filterRules";
+import { normalizeFeedUrl } from "@/utils/url";
+import { createHostLimiter } from "@/utils/http";
+import { cleanupOldArticles, getRetentionDays } from "@/services/retention";
+
+const DEFAULT_FETCH_TIMEOUT_SECS = 30;
+const DEFAULT_FETCH_MAX_RETRIES = 2;
+// Number of feeds refresh-all fetches in parallel
+const DEFAULT_REFRESH_CONCURRENCY = 6;
+// Gap between refresh-all requests to the same host
+const DEFAULT_PER_HOST_DELAY_MS = 500;
+
+/** Columns needed to refresh a feed, aliased to RefreshableFeed */
+export const REFRESHABLE_FEED_COLUMNS = `id, url, title,
+  prefer_summary as preferSummary, etag, last_modified as lastModified,
+  headers, last_fetched_at as lastFetchedAt,
+  refresh_interval_minutes as refreshIntervalMinutes, username, password,
+  retry_after as retryAfter`;
+
+export interface RefreshableFeed {
+  id: string;
+  url: string;
+  title: string;
+  preferSummary: number;
+  etag: string | null;
+  lastModified: string | null;
+  /** JSON object of extra request headers */
+  headers: string | null;
+  lastFetchedAt: number | null;
+  refreshIntervalMinutes: number | null;
+  username: string | null;
+  /** Encrypted, see services/credentials */
+  password: string | null;
+  /** Unix time before which the server asked not to be polled (429) */
+  retryAfter: number | null;
+}
+
+export interface SaveResult {
+  /** The newly saved articles */
+  saved: FeedArticle[];
+  /** How many of the saved articles were inserted unread */
+  unread: number;
+  /** Stored articles updated after an edit at the source */
+  updated: number;
+}
+
+/**
+ * Insert articles that are not yet stored for a feed, applying the filter
+ * rules for that feed to each new one. Feeds flagged auto_mark_read get
+ * their new articles inserted as read. With global_dedupe on, articles
+ * another feed already has are skipped. With update_existing_articles on,
+ * stored articles whose entry was edited at the source get the new title,
+ * content and summary; their read/starred state and fetched_at are kept,
+ * and articles replaced by their full web page are left alone
+ */
+export function saveArticles(
+  db: Database,
+  feedId: string,
+  articles: FeedArticle[],
+): SaveResult {
+  const now = Math.floor(Date.now() / 1000);
+
+  // Entries are matched by GUID when the feed provides one, else by link
+  const existing = db
+    .query("SELECT id, link, guid FROM articles WHERE feed_id = ?")
+    .all(feedId) as { id: string; link: string; guid: string | null }[];
+  const existingGuids = new Set(existing.map((row) => row.guid));
+  const existingLinks = new Set(existing.map((row) => row.link));
+  // Rows saved before GUIDs were recorded, by link
+  const legacyIds = new Map(
+    existing.filter((row) => !row.guid).map((row) => [row.link, row.id]),
+  );
+
+  const insertQuery = db.query(`
+    INSERT OR IGNORE INTO articles 
+    (id, feed_id, guid, title, link, content, summary, author, pub_date,
+     pub_date_raw, enclosure_url, enclosure_type, enclosure_length,
+     image_url, word_count, is_read, is_starred, fetched_at, source_hash)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  const backfillQuery = db.query("UPDATE articles SET guid = ? WHERE id = ?");
+  const updateExisting = getUpdateExistingArticles(db);
+  // Rows saved before hashes were recorded take the current entry as the
+  // last one seen rather than counting as edited
+  const recordHashQuery = db.query(
+    `UPDATE articles SET source_hash = ?
+     WHERE feed_id = ? AND guid = ? AND source_hash IS NULL`,
+  );
+  // Only entries that changed since the last refresh
+  const updateQuery = db.query(
+    `UPDATE articles SET title = ?, content = ?, summary = ?, word_count = ?,
+     source_hash = ?, updated_at = ?
+     WHERE feed_id = ? AND guid = ? AND source_hash != ? AND full_content = 0`,
+  );
+  const rules = db
+    .query(
+      `SELECT pattern, field, action FROM filter_rules
+       WHERE feed_id IS NULL OR feed_id = ?`,
+    )
+    .all(feedId) as Pick<FilterRule, "pattern" | "field" | "action">[];
+  const feedRow = db
+    .query("SELECT auto_mark_read as autoMarkRead FROM feeds WHERE id = ?")
+    .get(feedId) as { autoMarkRead: number | null } | null;
+  const autoMarkRead = feedRow?.autoMarkRead === 1;
+  const globalDedupe = getGlobalDedupe(db);
+  // Same post in another feed: an equivalent link, or the same title
+  // published at the same time
+  const duplicateQuery = db.query(
+    `SELECT 1 FROM articles WHERE feed_id != ?
+     AND (link IN (?, ?, ?) OR (pub_date = ? AND title = ?))
+     LIMIT 1`,
+  );
+
+  // One transaction for the whole feed: a single commit instead of one per
+  // article, and no half-imported feed if an insert fails
+  const insertAll = db.transaction((items: FeedArticle[]) => {
+    const saved: FeedArticle[] = [];
+    let updated = 0;
+    let unread = 0;
+
+    for (const article of items) {
+      if (article.guid) {
+        if (existingGuids.has(article.guid)) {
+          const hash = article.sourceHash ?? null;
+          recordHashQuery.run(hash, feedId, article.guid);
+          if (updateExisting && hash) {
+            updated += updateQuery.run(
+              article.title,
+              article.content,
+              article.summary || null,
+              article.wordCount ?? null,
+              hash,
+              now,
+              feedId,
+              article.guid,
+              hash,
+            ).changes;
+          }
+          continue;
+        }
+
+        const legacyId = legacyIds.get(article.link);
+        if (legacyId) {
+          backfillQuery.run(article.guid, legacyId);
+          legacyIds.delete(article.link);
+          continue;
+        }
+      } else if (existingLinks.has(article.link)) {
+        continue;
+      }
+
+      if (globalDedupe) {
+        const normalized = normalizeFeedUrl(article.link);
+        const duplicate = duplicateQuery.get(
+          feedId,
+          article.link,
+          normalized,
+          `${normalized}/`,
+          article.pubDate || now,
+          article.title,
+        );
+        if (duplicate) {
+          continue;
+        }
+      }
+
+      // Deleted by a rule: skipped now and again on every later refresh
+      const outcome = evaluateFilterRules(rules, article);
+      if (outcome.remove) {
+        continue;
+      }
+
+      const isRead = outcome.read || autoMarkRead;
+      insertQuery.run(
+        article.id,
+        feedId,
+        article.guid || null,
+        article.title,
+        article.link,
+        article.content,
+        article.summary || null,
+        article.author || null,
+        article.pubDate || now,
+        article.pubDateRaw || null,
+        article.enclosureUrl || null,
+        article.enclosureType || null,
+        article.enclosureLength || null,
+        article.imageUrl || null,
+        article.wordCount ?? null,
+        isRead ? 1 : 0,
+        outcome.starred ? 1 : 0,
+        now,
+        article.sourceHash ?? null,
+      );
+
+      // Feeds occasionally repeat an entry within one document
+      existingGuids.add(article.guid || null);
+      existingLinks.add(article.link);
+      saved.push(article);
+      if (!isRead) {
+        unread++;
+      }
+    }
+
+    db.query("UPDATE feeds SET updated_at = ? WHERE id = ?").run(now, feedId);
+
+    return { saved, unread, updated };
+  });
+
+  return insertAll(articles);
+}
+
+/**
+ * HTTP timeout for feed requests, from the fetch_timeout_secs setting
+ */
+export function getFetchTimeoutSecs(db: Database): number {
+  return getIntSetting(
+    db,
+    "fetch_timeout_secs",
+    DEFAULT_FETCH_TIMEOUT_SECS,
+    1,
+  );
+}
+
+/**
+ * Retries for transient fetch failures, from the fetch_max_retries setting
+ */
+export function getFetchMaxRetries(db: Database): number {
+  return getIntSetting(db, "fetch_max_retries", DEFAULT_FETCH_MAX_RETRIES);
+}
+
+/**
+ * Timeout, retries and User-Agent from the settings, for requests made
+ * outside a refresh
+ */
+export function getRequestOptions(db: Database): RequestOptions {
+  return {
+    timeoutSecs: getFetchTimeoutSecs(db),
+    maxRetries: getFetchMaxRetries(db),
+    userAgent: getUserAgent(db),
+  };
+}
+
+/**
+ * User-Agent for feed requests, from the user_agent setting
+ */
+export function getUserAgent(db: Database): string | undefined {
+  return getSetting(db, "user_agent")?.trim() || undefined;
+}
+
+/**
+ * Whether malformed feed entries are dropped, from the
+ * skip_invalid_entries setting; on unless set to "false" or "0"
+ */
+export function getSkipInvalidEntries(db: Database): boolean {
+  return getBoolSetting(db, "skip_invalid_entries", true);
+}
+
+/**
+ * Language stored for a feed, declared or detected on an earlier refresh
+ */
+function getFeedLanguage(db: Database, feedId: string): string | null {
+  const row = db
+    .query("SELECT language FROM feeds WHERE id = ?")
+    .get(feedId) as { language: string | null } | null;
+  return row?.language ?? null;
+}
+
+/**
+ * How feed entries are turned into articles, from the settings and the
+ * feed's prefer_summary flag
+ */
+export function getConvertOptions(
+  db: Database,
+  preferSummary = false,
+): ConvertOptions {
+  return {
+    preferSummary,
+    skipInvalid: getSkipInvalidEntries(db),
+    ...getSummaryLimits(db),
+  };
+}
+
+/**
+ * Generated summary length, from the summary_char_limit and
+ * summary_word_limit settings
+ */
+export function getSummaryLimits(db: Database): {
+  summaryCharLimit: number;
+  summaryWordLimit: number;
+} {
+  return {
+    summaryCharLimit: getIntSetting(
+      db,
+      "summary_char_limit",
+      DEFAULT_SUMMARY_CHAR_LIMIT,
+      1,
+    ),
+    summaryWordLimit: getIntSetting(
+      db,
+      "summary_word_limit",
+      DEFAULT_SUMMARY_WORD_LIMIT,
+      1,
+    ),
+  };
+}
+
+/**
+ * Whether articles already saved by another feed are skipped, from the
+ * global_dedupe setting
+ */
+export function getGlobalDedupe(db: Database): boolean {
+  return getBoolSetting(db, "global_dedupe", false);
+}
+
+/**
+ * Whether stored articles are updated when their entry is edited at the
+ * source, from the update_existing_articles setting
+ */
+export function getUpdateExistingArticles(db: Database): boolean {
+  return getBoolSetting(db, "update_existing_articles", false);
+}
+
+/**
+ * Whether each fetch's duration is stored on the feed, from the
+ * record_fetch_timing setting
+ */
+export function getRecordFetchTiming(db: Database): boolean {
+  return getBoolSetting(db, "record_fetch_timing", false);
+}
+
+/**
+ * Basic auth plus the feed's custom headers, which take precedence
+ */
+export function getFeedRequestHeaders(
+  feed: RefreshableFeed,
+): Record<string, string> | undefined {
+  const custom = parseFeedHeaders(feed.headers);
+  if (!feed.username) {
+    return custom;
+  }
+
+  const password = feed.password ? decryptSecret(feed.password) : "";
+  return {
+    Authorization: basicAuthorization(feed.username, password),
+    ...custom,
+  };
+}
+
+/**
+ * Parse the JSON stored in feeds.headers, ignoring malformed values
+ */
+export function parseFeedHeaders(
+  value: string | null,
+): Record<string, string> | undefined {
+  if (!value) return undefined;
+
+  try {
+    const parsed = JSON.parse(value);
+    return isHeaderMap(parsed) ? parsed : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+/**
+ * Whether a value is a plain object of string header values
+ */
+export function isHeaderMap(value: unknown): value is Record<string, string> {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    !Array.isArray(value) &&
+    Object.values(value).every((v) => typeof v === "string")
+  );
+}
+
+/**
+ * Fetch a feed using its stored validators and save any new articles
+ * The outcome is recorded in last_error / last_fetched_at
+ * @returns Newly saved, total fetched and skipped invalid entry counts, and
+ * the ids of the new articles; all empty when the server answered 304 Not
+ * Modified
+ */
+export async function refreshFeed(
+  db: Database,
+  feed: RefreshableFeed,
+): Promise<{
+  count: number;
+  total: number;
+  skipped: number;
+  newIds: string[];
+  /** Stored articles updated after an edit at the source */
+  updated: number;
+}> {
+  const startedAt = performance.now();
+  // Milliseconds since startedAt, or null when timing isn't recorded
+  const elapsed = () =>
+    getRecordFetchTiming(db) ? Math.round(performance.now() - startedAt) : null;
+
+  let result;
+  try {
+    result = await fetchFeed(feed.url, {
+      ...getConvertOptions(db, feed.preferSummary === 1),
+      etag: feed.etag,
+      lastModified: feed.lastModified,
+      timeoutSecs: getFetchTimeoutSecs(db),
+      maxRetries: getFetchMaxRetries(db),
+      userAgent: getUserAgent(db),
+      headers: getFeedRequestHeaders(feed),
+    });
+  } catch (error: any) {
+    // The scheduler leaves a rate limited feed alone until it may retry
+    const retryAfter =
+      error instanceof RateLimitedError && error.retryAfterMs !== null
+        ? Math.ceil((Date.now() + error.retryAfterMs) / 1000)
+        : null;
+    db.query(
+      `UPDATE feeds SET last_error = ?, last_fetch_ms = ?, retry_after = ?
+       WHERE id = ?`,
+    ).run(error.message || "Unknown error", elapsed(), retryAfter, feed.id);
+    throw error;
+  }
+
+  // Called once the articles are stored, so a failed save stays an error
+  const markFetched = () =>
+    db.query(
+      `UPDATE feeds SET last_error = NULL, last_fetched_at = ?,
+       last_fetch_ms = ?, retry_after = NULL WHERE id = ?`,
+    ).run(Math.floor(Date.now() / 1000), elapsed(), feed.id);
+
+  if (result.notModified) {
+    markFetched();
+    return { count: 0, total: 0, skipped: 0, newIds: [], updated: 0 };
+  }
+
+  // Follow permanent moves from now on, unless the new URL is already
+  // subscribed as another feed
+  if (result.movedTo) {
+    const moved = db
+      .query(
+        `UPDATE feeds SET url = ? WHERE id = ?
+         AND NOT EXISTS (SELECT 1 FROM feeds WHERE url = ?)`,
+      )
+      .run(result.movedTo, feed.id, result.movedTo);
+    if (moved.changes > 0) {
+      console.log(`[Feeds] '${feed.title}' moved to ${result.movedTo}`);
+    }
+  }
+
+  if (result.skipped > 0) {
+    console.log(
+      `[Feeds] '${feed.title}': ${result.skipped} entries skipped as invalid`,
+    );
+  }
+
+  let saveResult;
+  try {
+    saveResult = saveArticles(db, feed.id, result.articles);
+  } catch (error: any) {
+    db.query(
+      `UPDATE feeds SET last_error = ?, last_fetch_ms = ?, retry_after = NULL
+       WHERE id = ?`,
+    ).run(error.message || "Unknown error", elapsed(), feed.id);
+    throw error;
+  }
+  markFetched();
+
+  const { saved, unread, updated } = saveResult;
+  const count = saved.length;
+  if (updated > 0) {
+    console.log(`[Feeds] '${feed.title}': ${updated} edited articles updated`);
+  }
+  // Articles marked read on arrival are not news to the user
+  if (unread > 0) {
+    emitEvent("new-articles", { feedId: feed.id, count: unread });
+    queueNewArticlesNotification(db, feed.id, unread);
+  }
+  if (count > 0) {
+    const language = result.language ?? getFeedLanguage(db, feed.id);
+    const targetLang = getAutoTranslateTarget(db, language);
+    if (targetLang) {
+      // In the background so a slow provider doesn't hold up the refresh
+      autoTranslateArticles(db, saved, targetLang).catch((error) =>
+        console.error(
+          `[Feeds] Auto-translate failed for '${feed.title}':`,
+          error.message,
+        ),
+      );
+    }
+  }
+  db.query(
+    `UPDATE feeds SET etag = ?, last_modified = ?,
+     image_url = COALESCE(?, image_url), language = COALESCE(?, language)
+     WHERE id = ?`,
+  ).run(
+    result.etag,
+    result.lastModified,
+    result.imageUrl,
+    result.language,
+    feed.id,
+  );
+
+  return {
+    count,
+    total: result.articles.length,
+    skipped: result.skipped,
+    newIds: saved.map((article) => article.id),
+    updated,
+  };
+}
+
+/**
+ * Parallel feed fetches for refresh-all, from the refresh_concurrency setting
+ */
+export function getRefreshConcurrency(db: Database): number {
+  return getIntSetting(
+    db,
+    "refresh_concurrency",
+    DEFAULT_REFRESH_CONCURRENCY,
+    1,
+  );
+}
+
+/**
+ * Gap between requests to the same host during refresh-all, from the
+ * per_host_delay_ms setting. 0 turns the limit off
+ */
+export function getPerHostDelayMs(db: Database): number {
+  return getIntSetting(db, "per_host_delay_ms", DEFAULT_PER_HOST_DELAY_MS);
+}
+
+export interface RefreshAllResult {
+  count: number;
+  /** Ids of every article saved by the run */
+  newIds: string[];
+  errors: string[];
+  totalFeeds: number;
+  /** Outcome for each feed, in the order they were listed */
+  results: RefreshResult[];
+}
+
+/**
+ * Refresh every feed with a bounded worker pool, then apply the retention
+ * period. Failed feeds are reported in errors instead of aborting the run
+ */
+export async function refreshAllFeeds(
+  db: Database,
+): Promise<RefreshAllResult> {
+  const feeds = db
+    .query(`SELECT ${REFRESHABLE_FEED_COLUMNS} FROM feeds`)
+    .all() as RefreshableFeed[];
+
+  return refreshFeeds(db, feeds);
+}
+
+/**
+ * refreshAllFeeds for a subset of feeds
+ */
+export async function refreshFeeds(
+  db: Database,
+  feeds: RefreshableFeed[],
+): Promise<RefreshAllResult> {
+  console.log(`[Feeds] Found ${feeds.length} feeds to refresh`);
+
+  // Refresh with a pool of workers so at most refresh_concurrency
+  // requests are in flight at once
+  const concurrency = getRefreshConcurrency(db);
+  // Feeds sharing a host are spaced out so the server doesn't throttle us
+  const waitForHost = createHostLimiter(getPerHostDelayMs(db));
+  const results: RefreshResult[] = new Array(feeds.length);
+  let next = 0;
+
+  const worker = async () => {
+    while (next < feeds.length) {
+      const index = next++;
+      const feed = feeds[index];
+      const result: RefreshResult = {
+        feedId: feed.id,
+        title: feed.title,
+        newCount: 0,
+        newIds: [],
+        updated: 0,
+        skipped: 0,
+        error: null,
+      };
+      try {
+        await waitForHost(feed.url);
+        const { count, skipped, newIds, updated } = await refreshFeed(db, feed);
+        result.newCount = count;
+        result.newIds = newIds;
+        result.updated = updated;
+        result.skipped = skipped;
+      } catch (error: any) {
+        result.error = error.message || "Unknown error";
+        console.error(
+          `[Feeds] Failed to refresh feed '${feed.title}': ${result.error}`,
+        );
+      }
+      results[index] = result;
+    }
+  };
+
+  // One grouped notification for the whole run
+  const endNotificationBatch = beginNotificationBatch();
+  try {
+    await Promise.all(
+      Array.from({ length: Math.min(concurrency, feeds.length) }, worker),
+    );
+  } finally {
+    endNotificationBatch();
+  }
+
+  const count = results.reduce((sum, result) => sum + result.newCount, 0);
+  const errors = results
+    .filter((result) => result.error)
+    .map((result) => `${result.title}: ${result.error}`);
+
+  console.log(
+    `[Feeds] Refresh-all complete: ${count} new articles, ${errors.length} errors`,
+  );
+
+  // Prune old read articles when a retention period is configured
+  const retentionDays = getRetentionDays(db);
+  if (retentionDays > 0) {
+    const removed = cleanupOldArticles(db, retentionDays);
+    console.log(`[Feeds] Removed ${removed} articles older than retention`);
+  }
+
+  return {
+    count,
+    newIds: results.flatMap((result) => result.newIds),
+    errors,
+    totalFeeds: feeds.length,
+    results,
+  };
+}
+
+/**
+ * Fetch and parse every feed without saving articles, to find broken
+ * subscriptions. Uses the same concurrency, per-host spacing, retries and
+ * request settings as refresh
+ */
+export async function checkAllFeeds(db: Database): Promise<FeedHealth[]> {
+  const feeds = db
+    .query(`SELECT ${REFRESHABLE_FEED_COLUMNS} FROM feeds ORDER BY title`)
+    .all() as RefreshableFeed[];
+
+  const requestOptions = getRequestOptions(db);
+  const waitForHost = createHostLimiter(getPerHostDelayMs(db));
+  const results: FeedHealth[] = new Array(feeds.length);
+  let next = 0;
+
+  const worker = async () => {
+    while (next < feeds.length) {
+      const index = next++;
+      const feed = feeds[index];
+      const base = { feedId: feed.id, title: feed.title, url: feed.url };
+
+      try {
+        await waitForHost(feed.url);
+        results[index] = {
+          ...base,
+          ...(await checkFeedHealth(feed.url, {
+            ...requestOptions,
+            headers: getFeedRequestHeaders(feed),
+          })),
+        };
+      } catch (error: any) {
+        // e.g. a stored password that can no longer be decrypted
+        results[index] = {
+          ...base,
+          status: null,
+          parses: false,
+          articleCount: 0,
+          lastPubDate: null,
+          error: error.message || "Unknown error",
+        };
+      }
+    }
+  };
+
+  await Promise.all(
+    Array.from(
+      { length: Math.min(getRefreshConcurrency(db), feeds.length) },
+      worker,
+    ),
+  );
+
+  return results;
+}
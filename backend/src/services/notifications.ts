@@ -0,0 +1,84 @@
+import type { Database } from "bun:sqlite";
+import { getBoolSetting } from "@/db/settings";
+import { emitEvent } from "@/services/events";
+
+// Quiet period after the last new article before notifying, so feeds
+// refreshed one after another are grouped into a single notification
+const NOTIFY_DEBOUNCE_MS = 3000;
+
+// New article counts per feed since the last notification
+const pending = new Map<string, number>();
+let timer: ReturnType<typeof setTimeout> | null = null;
+let batchDepth = 0;
+
+/**
+ * Whether desktop notifications are on, from the notifications_enabled
+ * setting; off unless set to "true" or "1"
+ */
+export function getNotificationsEnabled(db: Database): boolean {
+  return getBoolSetting(db, "notifications_enabled", false);
+}
+
+/**
+ * Record newly saved articles for the next grouped notification
+ */
+export function queueNewArticlesNotification(
+  db: Database,
+  feedId: string,
+  count: number,
+): void {
+  if (count <= 0 || !getNotificationsEnabled(db)) return;
+
+  pending.set(feedId, (pending.get(feedId) || 0) + count);
+  if (batchDepth === 0) {
+    scheduleFlush();
+  }
+}
+
+/**
+ * Hold notifications until the returned function is called, e.g. for the
+ * duration of refresh-all
+ */
+export function beginNotificationBatch(): () => void {
+  batchDepth++;
+  if (timer) {
+    clearTimeout(timer);
+    timer = null;
+  }
+
+  let ended = false;
+  return () => {
+    if (ended) return;
+    ended = true;
+    batchDepth--;
+    if (batchDepth === 0 && pending.size > 0) {
+      flush();
+    }
+  };
+}
+
+function scheduleFlush(): void {
+  if (timer) {
+    clearTimeout(timer);
+  }
+  timer = setTimeout(flush, NOTIFY_DEBOUNCE_MS);
+}
+
+function flush(): void {
+  timer = null;
+  if (pending.size === 0) return;
+
+  let count = 0;
+  for (const feedCount of pending.values()) {
+    count += feedCount;
+  }
+  const feeds = pending.size;
+  pending.clear();
+
+  const articles = count === 1 ? "article" : "articles";
+  const feedLabel = feeds === 1 ? "feed" : "feeds";
+  emitEvent("notification", {
+    title: "RSS Reader",
+    body: `${count} new ${articles} across ${feeds} ${feedLabel}`,
+  });
+}
@@ -0,0 +1,37 @@
+import type { Database } from "bun:sqlite";
+import { getIntSetting } from "@/db/settings";
+
+/**
+ * Age in days after which read articles are pruned by refresh-all, from the
+ * retention_days setting; 0 disables automatic cleanup
+ */
+export function getRetentionDays(db: Database): number {
+  return getIntSetting(db, "retention_days", 0);
+}
+
+/**
+ * Delete read articles fetched more than `days` days ago, along with their
+ * cached translations. Kept articles are never removed, and starred ones
+ * only when keepStarred is false.
+ * @returns Number of deleted articles
+ */
+export function cleanupOldArticles(
+  db: Database,
+  days: number,
+  keepStarred = true,
+): number {
+  const cutoff = Math.floor(Date.now() / 1000) - days * 86400;
+  const where = `is_read = 1 AND is_kept = 0 AND fetched_at < ?${
+    keepStarred ? " AND is_starred = 0" : ""
+  }`;
+
+  const cleanup = db.transaction(() => {
+    db.query(
+      `DELETE FROM translations
+       WHERE article_id IN (SELECT id FROM articles WHERE ${where})`,
+    ).run(cutoff);
+    return db.query(`DELETE FROM articles WHERE ${where}`).run(cutoff).changes;
+  });
+
+  return cleanup();
+}
@@ -1,114 +1,996 @@
 import Parser from "rss-parser";
-import { randomUUID } from "crypto";
-import type { Article } from "@/types";
+import { createHash, randomUUID } from "crypto";
+import { gunzipSync } from "zlib";
+import { readFile } from "fs/promises";
+import { isAbsolute } from "path";
+import { fileURLToPath, pathToFileURL } from "url";
+import { version } from "../../package.json";
+import type {
+  Article,
+  FeedHealth,
+  FeedPreview,
+  FeedResponseInfo,
+} from "@/types";
+import { JSDOM } from "jsdom";
+import { Readability } from "@mozilla/readability";
 import { htmlToMarkdown } from "../utils/htmlToMarkdown";
+import {
+  collapseWhitespace,
+  countWords,
+  decodeEntities,
+  htmlToText,
+  truncateText,
+  truncateWords,
+} from "../utils/text";
+import { isJsonFeed, parseJsonFeed } from "../utils/jsonFeed";
+import { sanitizeHtml } from "../utils/sanitize";
+import { decodeFeedBytes } from "../utils/charset";
+import { parseLooseDate } from "../utils/date";
+import { parseRetryAfter, sleep } from "../utils/http";
+import { detectLanguage } from "../utils/language";
 
 const parser = new Parser({
   customFields: {
+    // Atom feed-level images
+    feed: ["icon", "logo"],
     item: [
       ["content:encoded", "contentEncoded"],
+      ["dc:date", "dcDate"],
       ["media:content", "mediaContent"],
+      ["media:thumbnail", "mediaThumbnail"],
     ],
   },
-  // Set timeout options
-  timeout: 10000, // 10 seconds timeout
 });
 
 const FETCH_TIMEOUT = 10000; // 10 seconds timeout for fetch
+const RETRY_BASE_DELAY = 1000; // Doubled after each failed attempt
+const MAX_REDIRECTS = 5;
+// Longest Retry-After waited out within a fetch; longer backoffs are left
+// to the scheduler
+const MAX_RETRY_AFTER_WAIT = 30000;
+export const DEFAULT_SUMMARY_CHAR_LIMIT = 200;
+export const DEFAULT_SUMMARY_WORD_LIMIT = 100;
 
-async function fetchWithTimeout(
+export const DEFAULT_USER_AGENT = `rss-reader/${version}`;
+
+const FEED_REQUEST_HEADERS = {
+  "User-Agent": DEFAULT_USER_AGENT,
+  Accept:
+    "application/rss+xml, application/xml, text/xml, application/atom+xml, */*",
+  // fetch decodes these transparently based on Content-Encoding
+  "Accept-Encoding": "gzip, deflate, br",
+};
+
+class FetchTimeoutError extends Error {
+  constructor(public timeoutMs: number) {
+    super(`Request timeout after ${timeoutMs}ms`);
+    this.name = "FetchTimeoutError";
+  }
+}
+
+/**
+ * The server answered 429 Too Many Requests
+ * retryAfterMs is null when it didn't say how long to wait
+ */
+export class RateLimitedError extends Error {
+  constructor(public retryAfterMs: number | null) {
+    super(
+      retryAfterMs === null
+        ? "Rate limited by the server"
+        : `Rate limited, retry after ${Math.ceil(retryAfterMs / 1000)}s`,
+    );
+    this.name = "RateLimitedError";
+  }
+}
+
+async function requestWithTimeout(
   url: string,
   timeoutMs: number,
-): Promise<string> {
+  headers: Record<string, string> = {},
+  redirect: RequestRedirect = "follow",
+): Promise<Response> {
   const controller = new AbortController();
   const timeoutId = setTimeout(() => controller.abort(), timeoutMs);
 
   try {
-    const response = await fetch(url, {
+    return await fetch(url, {
       signal: controller.signal,
-      headers: {
-        "User-Agent": "RSS-Reader/1.0",
-        Accept:
-          "application/rss+xml, application/xml, text/xml, application/atom+xml, */*",
-      },
+      headers: { ...FEED_REQUEST_HEADERS, ...headers },
+      redirect,
     });
+  } catch (error: any) {
+    if (error.name === "AbortError") {
+      throw new FetchTimeoutError(timeoutMs);
+    }
+    throw error;
+  } finally {
     clearTimeout(timeoutId);
+  }
+}
+
+interface FollowedResponse {
+  response: Response;
+  /** URL the response was finally served from */
+  url: string;
+  /** Every redirect on the way was permanent (301/308) */
+  permanent: boolean;
+}
+
+/**
+ * Follow redirects by hand so the chain can be capped at MAX_REDIRECTS and
+ * permanent moves told apart from temporary ones
+ */
+async function requestFollowingRedirects(
+  url: string,
+  timeoutMs: number,
+  headers: Record<string, string>,
+): Promise<FollowedResponse> {
+  let current = url;
+  let permanent = true;
+  let requestHeaders = headers;
+
+  for (let hops = 0; ; hops++) {
+    const response = await requestWithTimeout(
+      current,
+      timeoutMs,
+      requestHeaders,
+      "manual",
+    );
+    const location = response.headers.get("location");
+
+    // 304 Not Modified is a 3xx without a Location
+    if (response.status < 300 || response.status >= 400 || !location) {
+      return { response, url: current, permanent };
+    }
+
+    await response.body?.cancel();
+    if (hops >= MAX_REDIRECTS) {
+      throw new Error(`Too many redirects (more than ${MAX_REDIRECTS})`);
+    }
+
+    permanent &&= response.status === 301 || response.status === 308;
+    const next = new URL(location, current);
+    // Like fetch, don't send credentials on to another origin
+    if (next.origin !== new URL(current).origin) {
+      requestHeaders = withoutAuthorization(requestHeaders);
+    }
+    current = next.href;
+  }
+}
+
+function withoutAuthorization(
+  headers: Record<string, string>,
+): Record<string, string> {
+  return Object.fromEntries(
+    Object.entries(headers).filter(
+      ([name]) => name.toLowerCase() !== "authorization",
+    ),
+  );
+}
+
+/**
+ * requestFollowingRedirects, retried with exponential backoff on network
+ * errors and 5xx responses, and after the Retry-After delay on 429 responses
+ * when it is short. Timeouts and other 4xx responses are returned
+ * immediately.
+ */
+async function requestWithRetry(
+  url: string,
+  timeoutMs: number,
+  headers: Record<string, string>,
+  maxRetries: number,
+): Promise<FollowedResponse> {
+  for (let attempt = 0; ; attempt++) {
+    const canRetry = attempt < maxRetries;
+
+    try {
+      const result = await requestFollowingRedirects(url, timeoutMs, headers);
+      const { status } = result.response;
+
+      if (status === 429 && canRetry) {
+        const retryAfter = parseRetryAfter(
+          result.response.headers.get("retry-after"),
+        );
+        if (retryAfter !== null && retryAfter <= MAX_RETRY_AFTER_WAIT) {
+          console.warn(
+            `[RSS] ${url} rate limited, retrying in ${retryAfter / 1000}s`,
+          );
+          await result.response.body?.cancel();
+          await sleep(retryAfter);
+          continue;
+        }
+      }
+
+      if (status < 500 || !canRetry) {
+        return result;
+      }
+
+      console.warn(`[RSS] ${url} returned ${status}, retrying`);
+      await result.response.body?.cancel();
+    } catch (error: any) {
+      if (error instanceof FetchTimeoutError || !canRetry) {
+        throw error;
+      }
+
+      console.warn(`[RSS] ${url} failed (${error.message}), retrying`);
+    }
+
+    await sleep(RETRY_BASE_DELAY * 2 ** attempt);
+  }
+}
+
+/**
+ * Read a feed response as text, decoded with the charset from the headers
+ * or XML declaration
+ * Some servers send gzipped files (e.g. feed.xml.gz) without a
+ * Content-Encoding header, so fetch leaves them compressed
+ */
+async function readFeedBody(response: Response): Promise<string> {
+  return decodeFeedBody(
+    new Uint8Array(await response.arrayBuffer()),
+    response.headers.get("content-type"),
+  );
+}
+
+function decodeFeedBody(
+  bytes: Uint8Array,
+  contentType: string | null,
+): string {
+  const isGzip = bytes[0] === 0x1f && bytes[1] === 0x8b;
+  return decodeFeedBytes(isGzip ? gunzipSync(bytes) : bytes, contentType);
+}
+
+/**
+ * Filesystem path for file:// URLs and bare absolute paths, which are read
+ * from disk instead of requested
+ */
+export function getLocalFeedPath(url: string): string | null {
+  if (/^file:/i.test(url)) {
+    return fileURLToPath(url);
+  }
+  return isAbsolute(url) ? url : null;
+}
+
+/**
+ * Read a feed file, decoded by its BOM or XML encoding declaration
+ */
+export async function readLocalFeed(path: string): Promise<string> {
+  try {
+    return decodeFeedBody(new Uint8Array(await readFile(path)), null);
+  } catch (error: any) {
+    throw new Error(`Failed to read feed file: ${error.message}`);
+  }
+}
+
+/**
+ * The URL served an HTML page (often a "site down" page sent with a 200)
+ * where a feed was expected
+ */
+export class WebPageError extends Error {
+  constructor() {
+    super("URL returned a web page, not a feed");
+    this.name = "WebPageError";
+  }
+}
+
+// An HTML document, after any BOM, whitespace and leading comments
+const HTML_DOCUMENT =
+  /^\uFEFF?\s*(?:<!--[\s\S]*?-->\s*)*<(?:!doctype\s+html|html[\s>])/i;
+
+/**
+ * Parse a feed document: JSON Feed when it looks like JSON, else RSS/Atom
+ * @throws WebPageError for HTML pages, which the XML parser would only
+ * reject with a confusing message
+ */
+async function parseFeedDocument(
+  body: string,
+  contentType?: string | null,
+): Promise<Parser.Output<any>> {
+  if (HTML_DOCUMENT.test(body)) {
+    throw new WebPageError();
+  }
+
+  return isJsonFeed(body, contentType)
+    ? parseJsonFeed(body)
+    : parser.parseString(body);
+}
+
+/** Request settings shared by refreshes and the other feed lookups */
+export type RequestOptions = Pick<
+  FetchOptions,
+  "timeoutSecs" | "userAgent" | "headers" | "maxRetries"
+>;
+
+/**
+ * Download a page or feed like a refresh does (redirect cap, retries,
+ * gzip and charset decoding)
+ * @returns The decoded body and the URL it was finally served from
+ */
+async function fetchBody(
+  url: string,
+  options: RequestOptions,
+): Promise<{ body: string; url: string }> {
+  const { response, url: finalUrl } = await requestWithRetry(
+    url,
+    getTimeoutMs(options),
+    getRequestHeaders(options),
+    options.maxRetries ?? 0,
+  );
+
+  if (!response.ok) {
+    await response.body?.cancel();
+    throw new Error(`HTTP ${response.status}: ${response.statusText}`);
+  }
+
+  return { body: await readFeedBody(response), url: finalUrl };
+}
+
+function getTimeoutMs(options: RequestOptions): number {
+  return options.timeoutSecs ? options.timeoutSecs * 1000 : FETCH_TIMEOUT;
+}
+
+function getRequestHeaders(options: RequestOptions): Record<string, string> {
+  const headers: Record<string, string> = { ...options.headers };
+  if (options.userAgent) headers["User-Agent"] = options.userAgent;
+  return headers;
+}
+
+/**
+ * Request a feed URL the way a refresh does and report what the server
+ * returned, without parsing or saving anything
+ */
+export async function inspectFeedResponse(
+  url: string,
+  options: RequestOptions = {},
+): Promise<FeedResponseInfo> {
+  const { response, url: finalUrl } = await requestFollowingRedirects(
+    url,
+    getTimeoutMs(options),
+    getRequestHeaders(options),
+  );
+
+  // Only the headers are needed
+  await response.body?.cancel();
+
+  const contentLength = response.headers.get("content-length");
+
+  return {
+    finalUrl,
+    redirected: finalUrl !== url,
+    status: response.status,
+    statusText: response.statusText,
+    contentType: response.headers.get("content-type"),
+    contentLength: contentLength ? parseInt(contentLength) : null,
+    etag: response.headers.get("etag"),
+    lastModified: response.headers.get("last-modified"),
+    cacheControl: response.headers.get("cache-control"),
+  };
+}
+
+/**
+ * Fetch and parse a feed URL without saving anything, so typos and non-feed
+ * pages are caught before subscribing
+ */
+export async function previewFeed(
+  url: string,
+  headers: Record<string, string> = {},
+): Promise<FeedPreview> {
+  const localPath = getLocalFeedPath(url);
+  let finalUrl = url;
+  let body: string;
+  let contentType: string | null = null;
+
+  if (localPath) {
+    body = await readLocalFeed(localPath);
+  } else {
+    const result = await requestFollowingRedirects(url, FETCH_TIMEOUT, headers);
+    const { response } = result;
 
     if (!response.ok) {
+      await response.body?.cancel();
       throw new Error(`HTTP ${response.status}: ${response.statusText}`);
     }
 
-    return await response.text();
+    finalUrl = result.url;
+    body = await readFeedBody(response);
+    contentType = response.headers.get("content-type");
+  }
+
+  let feed;
+  try {
+    feed = await parseFeedDocument(body, contentType);
+  } catch (error) {
+    if (error instanceof WebPageError) {
+      throw error;
+    }
+    throw new Error("Not a valid RSS, Atom or JSON feed");
+  }
+
+  return {
+    url: finalUrl,
+    title: decodeEntities(feed.title || "").trim() || null,
+    description: decodeEntities(feed.description || "").trim() || null,
+    siteUrl: getSiteUrl(feed, finalUrl) || null,
+    articleCount: feed.items.length,
+  };
+}
+
+/**
+ * Fetch and parse a feed for diagnostics, without saving anything
+ * Transient failures are retried like a refresh; the rest are reported in
+ * the result rather than thrown
+ */
+export async function checkFeedHealth(
+  url: string,
+  options: RequestOptions = {},
+): Promise<Omit<FeedHealth, "feedId" | "title" | "url">> {
+  const health: Omit<FeedHealth, "feedId" | "title" | "url"> = {
+    status: null,
+    parses: false,
+    articleCount: 0,
+    lastPubDate: null,
+    error: null,
+  };
+
+  try {
+    const localPath = getLocalFeedPath(url);
+    let feed;
+    if (localPath) {
+      feed = await parseFeedDocument(await readLocalFeed(localPath));
+    } else {
+      const { response } = await requestWithRetry(
+        url,
+        getTimeoutMs(options),
+        getRequestHeaders(options),
+        options.maxRetries ?? 0,
+      );
+      health.status = response.status;
+
+      if (!response.ok) {
+        await response.body?.cancel();
+        health.error = `HTTP ${response.status}: ${response.statusText}`;
+        return health;
+      }
+
+      feed = await parseFeedDocument(
+        await readFeedBody(response),
+        response.headers.get("content-type"),
+      );
+    }
+    health.parses = true;
+    health.articleCount = feed.items.length;
+
+    const dates = feed.items
+      .map((item) => new Date(item.isoDate || "").getTime())
+      .filter((time) => !isNaN(time));
+    if (dates.length > 0) {
+      health.lastPubDate = Math.floor(Math.max(...dates) / 1000);
+    }
   } catch (error: any) {
-    clearTimeout(timeoutId);
-    if (error.name === "AbortError") {
-      throw new Error(`Request timeout after ${timeoutMs}ms`);
+    health.error =
+      error instanceof FetchTimeoutError
+        ? `Feed timed out after ${error.timeoutMs / 1000}s`
+        : error.message || "Unknown error";
+  }
+
+  return health;
+}
+
+/**
+ * Download an article page and extract its main body with Readability,
+ * for feeds that only publish a summary. Returns sanitized Markdown
+ */
+export async function fetchFullContent(
+  url: string,
+): Promise<{ content: string; wordCount: number }> {
+  const { response, url: finalUrl } = await requestFollowingRedirects(
+    url,
+    FETCH_TIMEOUT,
+    { Accept: "text/html, application/xhtml+xml, */*" },
+  );
+
+  if (!response.ok) {
+    await response.body?.cancel();
+    throw new Error(`HTTP ${response.status}: ${response.statusText}`);
+  }
+
+  // The page URL lets Readability resolve relative links and images
+  const document = new JSDOM(await response.text(), { url: finalUrl }).window
+    .document;
+  const article = new Readability(document).parse();
+
+  if (!article?.content) {
+    throw new Error("Could not find the article content on the page");
+  }
+
+  const html = sanitizeHtml(article.content);
+  return {
+    content: htmlToMarkdown(html),
+    wordCount: countWords(htmlToText(html)),
+  };
+}
+
+const FEED_LINK_TYPES = ["application/rss+xml", "application/atom+xml"];
+const COMMON_FEED_PATHS = [
+  "/feed",
+  "/rss",
+  "/atom.xml",
+  "/feed.xml",
+  "/rss.xml",
+];
+
+/**
+ * Find candidate feed URLs for a website
+ * Uses <link rel="alternate"> tags, falling back to common feed paths
+ */
+export async function discoverFeeds(
+  url: string,
+  options: RequestOptions = {},
+): Promise<string[]> {
+  const { body, url: pageUrl } = await fetchBody(url, options);
+
+  // The URL may already be a feed
+  if (await isParsableFeed(body)) {
+    return [url];
+  }
+
+  const document = new JSDOM(body).window.document;
+  const candidates = new Set<string>();
+
+  for (const link of Array.from(document.querySelectorAll("link[href]"))) {
+    const rel = (link.getAttribute("rel") || "").toLowerCase().split(/\s+/);
+    const type = (link.getAttribute("type") || "").toLowerCase();
+
+    if (rel.includes("alternate") && FEED_LINK_TYPES.includes(type)) {
+      try {
+        candidates.add(new URL(link.getAttribute("href")!, pageUrl).href);
+      } catch {
+        // Ignore malformed hrefs
+      }
+    }
+  }
+
+  if (candidates.size > 0) {
+    return [...candidates];
+  }
+
+  for (const path of COMMON_FEED_PATHS) {
+    const candidate = new URL(path, pageUrl).href;
+    try {
+      const { body: candidateBody } = await fetchBody(candidate, options);
+      if (await isParsableFeed(candidateBody)) {
+        candidates.add(candidate);
+      }
+    } catch {
+      // Path doesn't exist or isn't reachable
     }
-    throw error;
   }
+
+  return [...candidates];
+}
+
+async function isParsableFeed(body: string): Promise<boolean> {
+  try {
+    await parseFeedDocument(body);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+export type FeedArticle = Omit<
+  Article,
+  "feedId" | "isRead" | "isStarred" | "isKept" | "fetchedAt"
+> & {
+  /** Entry id/GUID, used to recognize an entry across refreshes */
+  guid?: string;
+  /** Date string as written in the feed, kept even when it doesn't parse */
+  pubDateRaw?: string;
+  /** Hash of the entry's raw title and content, see getSourceHash */
+  sourceHash?: string;
+};
+
+export interface ConvertOptions {
+  /** Prefer the entry summary over its full content */
+  preferSummary?: boolean;
+  /**
+   * Drop entries without a usable link, or with neither a title nor any
+   * content, instead of saving them as "Untitled" placeholders
+   */
+  skipInvalid?: boolean;
+  /** Limits for summaries generated from the content */
+  summaryCharLimit?: number;
+  summaryWordLimit?: number;
+}
+
+interface ConvertedItems {
+  articles: FeedArticle[];
+  /** Entries dropped by skipInvalid */
+  skipped: number;
+}
+
+export interface FetchOptions extends ConvertOptions {
+  /** Validators from the previous fetch, sent as conditional GET headers */
+  etag?: string | null;
+  lastModified?: string | null;
+  /** Request timeout, defaults to FETCH_TIMEOUT */
+  timeoutSecs?: number;
+  /** Overrides DEFAULT_USER_AGENT */
+  userAgent?: string;
+  /** Extra headers for this feed, e.g. cookies or an API key */
+  headers?: Record<string, string>;
+  /** Retries after network errors and 5xx responses, default none */
+  maxRetries?: number;
 }
 
+export type FeedFetchResult =
+  | { notModified: true }
+  | {
+      notModified: false;
+      articles: FeedArticle[];
+      /** Malformed entries left out, see ConvertOptions.skipInvalid */
+      skipped: number;
+      /** New feed URL when the old one permanently redirects there */
+      movedTo: string | null;
+      /** Declared language tag, else one detected from the entries */
+      language: string | null;
+      /** Feed logo or icon, else the site's favicon */
+      imageUrl: string | null;
+      etag: string | null;
+      lastModified: string | null;
+    };
+
+/**
+ * Fetch and parse a feed. Every request goes through the same fetch-based
+ * path (redirect cap, retries, timeout) so concurrent refreshes share one
+ * connection pool. Local files (file:// or an absolute path) are read from
+ * disk instead.
+ */
 export async function fetchFeed(
   url: string,
-): Promise<Omit<Article, "feedId" | "isRead" | "isStarred" | "fetchedAt">[]> {
-  let feed;
+  options: FetchOptions = {},
+): Promise<FeedFetchResult> {
+  const localPath = getLocalFeedPath(url);
+  if (localPath) {
+    return fetchLocalFeed(localPath, options);
+  }
+
+  const timeoutMs = getTimeoutMs(options);
+
+  const headers = getRequestHeaders(options);
+  if (options.etag) headers["If-None-Match"] = options.etag;
+  if (options.lastModified) {
+    headers["If-Modified-Since"] = options.lastModified;
+  }
 
+  let result;
   try {
-    // Try to fetch with timeout first
-    const xml = await fetchWithTimeout(url, FETCH_TIMEOUT);
-    feed = await parser.parseString(xml);
-  } catch (fetchError: any) {
-    // If fetch with timeout fails, try parser's default method as fallback
-    try {
-      feed = await parser.parseURL(url);
-    } catch (parseError: any) {
-      // If both fail, throw the original error
-      throw new Error(
-        `Failed to fetch feed: ${fetchError.message || parseError.message}`,
-      );
+    result = await requestWithRetry(
+      url,
+      timeoutMs,
+      headers,
+      options.maxRetries ?? 0,
+    );
+  } catch (error: any) {
+    if (error instanceof FetchTimeoutError) {
+      throw new Error(`Feed timed out after ${timeoutMs / 1000}s`);
     }
+    throw new Error(`Failed to fetch feed: ${error.message}`);
+  }
+  const { response } = result;
+
+  // Nothing changed since the last fetch
+  if (response.status === 304) {
+    return { notModified: true };
+  }
+
+  if (response.status === 429) {
+    await response.body?.cancel();
+    throw new RateLimitedError(
+      parseRetryAfter(response.headers.get("retry-after")),
+    );
+  }
+
+  if (!response.ok) {
+    await response.body?.cancel();
+    throw new Error(`HTTP ${response.status}: ${response.statusText}`);
+  }
+
+  const sourceUrl = result.url;
+  const movedTo = result.permanent && result.url !== url ? result.url : null;
+  const feed = await parseFeedDocument(
+    await readFeedBody(response),
+    response.headers.get("content-type"),
+  );
+
+  const { articles, skipped } = convertFeedItems(feed, options, sourceUrl);
+
+  return {
+    notModified: false,
+    articles,
+    skipped,
+    movedTo,
+    language: getFeedLanguage(feed, articles),
+    imageUrl: getFeedImageUrl(feed, sourceUrl),
+    etag: response.headers.get("etag"),
+    lastModified: response.headers.get("last-modified"),
+  };
+}
+
+async function fetchLocalFeed(
+  path: string,
+  options: ConvertOptions,
+): Promise<FeedFetchResult> {
+  const sourceUrl = pathToFileURL(path).href;
+  const feed = await parseFeedDocument(await readLocalFeed(path));
+  const { articles, skipped } = convertFeedItems(feed, options, sourceUrl);
+
+  return {
+    notModified: false,
+    articles,
+    skipped,
+    movedTo: null,
+    language: getFeedLanguage(feed, articles),
+    imageUrl: getFeedImageUrl(feed, sourceUrl),
+    etag: null,
+    lastModified: null,
+  };
+}
+
+/**
+ * Parse an already-downloaded RSS/Atom or JSON Feed document
+ * Relative entry links are resolved against the site the feed declares,
+ * else against sourceUrl when given
+ */
+export async function parseFeed(
+  xml: string,
+  options: ConvertOptions = {},
+  sourceUrl?: string,
+): Promise<FeedArticle[]> {
+  const feed = await parseFeedDocument(xml);
+  return convertFeedItems(feed, options, sourceUrl).articles;
+}
+
+/**
+ * Resolve a relative URL against base
+ * Absolute URLs are returned verbatim so stored links keep matching
+ */
+function resolveUrl(url: string, base?: string): string {
+  if (/^[a-z][a-z\d+.-]*:/i.test(url)) {
+    return url;
+  }
+
+  try {
+    return new URL(url, base).href;
+  } catch {
+    return url;
+  }
+}
+
+/**
+ * The site a feed belongs to, which entry links are relative to
+ * The declared site link may itself be relative to the feed URL
+ */
+function getSiteUrl(
+  feed: Parser.Output<any>,
+  sourceUrl?: string,
+): string | undefined {
+  return feed.link ? resolveUrl(feed.link, sourceUrl) : sourceUrl;
+}
+
+/**
+ * The feed's <language>, or a guess from its first entries' text
+ */
+function getFeedLanguage(
+  feed: Parser.Output<any>,
+  articles: FeedArticle[],
+): string | null {
+  const declared = (feed as any).language?.trim();
+  if (declared) {
+    return declared;
+  }
+
+  const sample = articles
+    .slice(0, 3)
+    .map((article) => `${article.title} ${article.summary || ""}`)
+    .join(" ");
+  return detectLanguage(sample);
+}
+
+/**
+ * Pick an icon for a feed: the RSS image or Atom logo/icon when present,
+ * otherwise /favicon.ico on the site's domain
+ */
+function getFeedImageUrl(
+  feed: Parser.Output<any>,
+  sourceUrl?: string,
+): string | null {
+  const declared = feed.image?.url || (feed as any).logo || (feed as any).icon;
+  if (declared) {
+    return resolveUrl(declared, sourceUrl);
   }
 
+  const siteUrl = getSiteUrl(feed, sourceUrl);
+  if (!siteUrl) return null;
+
+  try {
+    const { protocol, origin } = new URL(siteUrl);
+    return protocol.startsWith("http") ? `${origin}/favicon.ico` : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Media attached to an entry: the RSS enclosure, else the first
+ * media:content element
+ */
+function getEnclosure(
+  item: Parser.Item,
+  baseUrl?: string,
+): Pick<FeedArticle, "enclosureUrl" | "enclosureType" | "enclosureLength"> {
+  const media = [(item as any).mediaContent].flat()[0]?.$;
+  const enclosure: any = item.enclosure?.url ? item.enclosure : media;
+  if (!enclosure?.url) {
+    return {};
+  }
+
+  const length = Number(enclosure.length || enclosure.fileSize);
+  return {
+    enclosureUrl: resolveUrl(enclosure.url, baseUrl),
+    enclosureType: enclosure.type || undefined,
+    enclosureLength: length > 0 ? length : undefined,
+  };
+}
+
+/**
+ * Thumbnail for an entry: a media:thumbnail or image media:content, else
+ * the first <img> in the content, resolved against the article link
+ */
+function getLeadImage(
+  item: Parser.Item,
+  html: string,
+  link: string,
+): string | undefined {
+  const thumbnail = [(item as any).mediaThumbnail].flat()[0]?.$?.url;
+  const media = [(item as any).mediaContent]
+    .flat()
+    .map((m) => m?.$)
+    .find((m) => m?.medium === "image" || m?.type?.startsWith("image/"));
+  const image =
+    thumbnail ||
+    media?.url ||
+    html.match(/<img\b[^>]*?\bsrc\s*=\s*["']([^"']+)["']/i)?.[1];
+
+  return image ? resolveUrl(image, link) : undefined;
+}
+
+function convertFeedItems(
+  feed: Parser.Output<any>,
+  options: ConvertOptions,
+  sourceUrl?: string,
+): ConvertedItems {
   const now = Math.floor(Date.now() / 1000);
+  const baseUrl = getSiteUrl(feed, sourceUrl);
+  let skipped = 0;
 
-  return feed.items.map((item) => {
-    const htmlContent =
-      (item as any).contentEncoded || item.content || item.summary || "";
+  const articles = feed.items.flatMap((item): FeedArticle[] => {
+    // Content first by default; some feeds keep cleaner text in the summary
+    const candidates = [
+      (item as any).contentEncoded,
+      item.content,
+      item.summary,
+    ];
+    if (options.preferSummary) {
+      candidates.reverse();
+    }
+    const rawContent = candidates.find(Boolean) || "";
+    // Feed HTML is untrusted: drop scripts and handlers before using it
+    const htmlContent = sanitizeHtml(rawContent);
     const htmlSummary =
-      item.summary && item.summary !== htmlContent ? item.summary : "";
+      item.summary && item.summary !== rawContent
+        ? sanitizeHtml(item.summary)
+        : "";
 
     // Convert HTML to Markdown
     const content = htmlToMarkdown(htmlContent);
+    // Summary-only feeds are counted from whatever text they provide
+    const text = htmlToText(htmlContent);
     const summary = htmlSummary
       ? htmlToMarkdown(htmlSummary)
-      : createSummary(content);
-
-    return {
-      id: randomUUID(),
-      title: item.title || "Untitled",
-      link: item.link || item.guid || randomUUID(),
-      content,
-      summary,
-      author: item.creator || feed.title,
-      pubDate: item.isoDate
-        ? Math.floor(new Date(item.isoDate).getTime() / 1000)
-        : now,
-    };
+      : truncateText(
+          truncateWords(
+            text,
+            options.summaryWordLimit ?? DEFAULT_SUMMARY_WORD_LIMIT,
+          ),
+          options.summaryCharLimit ?? DEFAULT_SUMMARY_CHAR_LIMIT,
+        );
+
+    const title = collapseWhitespace(decodeEntities(item.title || ""));
+    // A permalink GUID can stand in for a missing link
+    const permalink = item.link || (isHttpUrl(item.guid) ? item.guid : null);
+
+    if (options.skipInvalid && (!permalink || (!title && !text.trim()))) {
+      skipped++;
+      return [];
+    }
+
+    const link = permalink
+      ? resolveUrl(permalink, baseUrl)
+      : item.guid || randomUUID();
+    const rawDate = getRawDate(item);
+
+    return [
+      {
+        id: randomUUID(),
+        guid: item.guid || (item as any).id || undefined,
+        ...getEnclosure(item, baseUrl),
+        imageUrl: getLeadImage(item, htmlContent, link),
+        title: title || "Untitled",
+        link,
+        content,
+        summary,
+        wordCount: countWords(text),
+        author: collapseWhitespace(
+          decodeEntities(item.creator || feed.title || ""),
+        ),
+        pubDateRaw: rawDate,
+        sourceHash: getSourceHash(item),
+        pubDate: item.isoDate
+          ? Math.floor(new Date(item.isoDate).getTime() / 1000)
+          : parseFallbackDate(rawDate) ?? now,
+      },
+    ];
   });
+
+  return { articles, skipped };
+}
+
+/**
+ * Hash of an entry's title and content as the feed sent them, before any
+ * conversion, so changes to summary settings don't look like edits
+ */
+function getSourceHash(item: Parser.Item): string {
+  const fields = [
+    item.title,
+    (item as any).contentEncoded,
+    item.content,
+    item.summary,
+  ];
+  return createHash("sha256").update(JSON.stringify(fields)).digest("hex");
 }
 
-function createSummary(markdown: string): string {
-  // Markdown is already clean text, just limit length
-  const text = markdown
-    .replace(/[#*_[\]()]/g, "") // Remove markdown syntax
-    .replace(/\s+/g, " ")
-    .trim();
+/**
+ * The entry's date field as written, before any parsing
+ */
+function getRawDate(item: Parser.Item): string | undefined {
+  const raw = [
+    item.pubDate,
+    (item as any).dcDate,
+    (item as any).published,
+    (item as any).updated,
+  ].find((value) => typeof value === "string" && value.trim());
+  return raw?.trim();
+}
 
-  const words = text.split(" ").slice(0, 100).join(" ");
+/**
+ * Date for entries whose date the feed parser could not read
+ */
+function parseFallbackDate(raw: string | undefined): number | null {
+  if (!raw) {
+    return null;
+  }
 
-  if (words.length > 200) {
-    return words.slice(0, 200) + "...";
+  const parsed = parseLooseDate(raw);
+  if (parsed === null) {
+    console.warn(`[RSS] Unrecognized date "${raw}", using fetch time`);
+  } else {
+    const iso = new Date(parsed * 1000).toISOString();
+    console.log(`[RSS] Parsed nonstandard date "${raw}" as ${iso}`);
   }
+  return parsed;
+}
 
-  return words;
+function isHttpUrl(value: string | undefined): value is string {
+  return !!value && /^https?:\/\//i.test(value);
 }
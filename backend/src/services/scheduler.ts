@@ -0,0 +1,105 @@
+import type { Database } from "bun:sqlite";
+import { getDatabase } from "@/db/connection";
+import { getIntSetting } from "@/db/settings";
+import { emitEvent } from "@/services/events";
+import {
+  REFRESHABLE_FEED_COLUMNS,
+  refreshFeeds,
+  type RefreshableFeed,
+} from "@/services/feedSync";
+
+// How often the scheduler looks for feeds that are due
+const IDLE_CHECK_MS = 60 * 1000;
+
+let running = false;
+let timer: ReturnType<typeof setTimeout> | null = null;
+// Last auto-refresh attempt per feed (unix seconds), so a failing feed
+// waits a full interval instead of being retried on every check
+const lastAttemptAt = new Map<string, number>();
+
+/**
+ * Minutes between background refreshes, from the
+ * auto_refresh_interval_minutes setting; 0 leaves feeds without their own
+ * interval alone
+ */
+export function getAutoRefreshIntervalMinutes(db: Database): number {
+  return getIntSetting(db, "auto_refresh_interval_minutes", 0);
+}
+
+/**
+ * Feeds whose last fetch (or failed attempt) is at least their own
+ * refresh interval ago; feeds without one use the global interval, and are
+ * never due when it is 0
+ */
+function getDueFeeds(
+  db: Database,
+  globalMinutes: number,
+): RefreshableFeed[] {
+  const now = Math.floor(Date.now() / 1000);
+  const feeds = db
+    .query(`SELECT ${REFRESHABLE_FEED_COLUMNS} FROM feeds`)
+    .all() as RefreshableFeed[];
+
+  return feeds.filter((feed) => {
+    if (feed.retryAfter && feed.retryAfter > now) {
+      return false;
+    }
+    const minutes = feed.refreshIntervalMinutes ?? globalMinutes;
+    if (minutes <= 0) {
+      return false;
+    }
+    const last = Math.max(
+      feed.lastFetchedAt ?? 0,
+      lastAttemptAt.get(feed.id) ?? 0,
+    );
+    return last + minutes * 60 <= now;
+  });
+}
+
+async function tick(): Promise<void> {
+  timer = null;
+
+  try {
+    const db = getDatabase();
+    // Feeds with their own interval are checked even when the global
+    // interval is 0
+    const feeds = getDueFeeds(db, getAutoRefreshIntervalMinutes(db));
+
+    if (feeds.length > 0) {
+      console.log(`[Scheduler] Auto-refreshing ${feeds.length} due feeds...`);
+      const now = Math.floor(Date.now() / 1000);
+      for (const feed of feeds) {
+        lastAttemptAt.set(feed.id, now);
+      }
+      const { count } = await refreshFeeds(db, feeds);
+      emitEvent("feeds-refreshed", { count });
+    }
+  } catch (error: any) {
+    console.error("[Scheduler] Auto-refresh failed:", error.message);
+  }
+
+  if (running) {
+    timer = setTimeout(tick, IDLE_CHECK_MS);
+  }
+}
+
+/**
+ * Start the background auto-refresh loop
+ */
+export function startScheduler(): void {
+  if (running) return;
+  running = true;
+  timer = setTimeout(tick, IDLE_CHECK_MS);
+}
+
+/**
+ * Stop the loop; a refresh already in progress finishes but is not
+ * rescheduled
+ */
+export function stopScheduler(): void {
+  running = false;
+  if (timer) {
+    clearTimeout(timer);
+    timer = null;
+  }
+}
@@ -1,18 +1,137 @@
-interface TranslationSettings {
+import type { Database } from "bun:sqlite";
+import { createHash } from "crypto";
+import { parseRetryAfter, sleep } from "@/utils/http";
+import { getIntSetting, getSettingOr } from "@/db/settings";
+
+export interface TranslationSettings {
   baseUrl: string;
   apiKey: string;
   model: string;
   prompt: string;
+  timeoutSeconds: number;
+  /** From translation_provider; null when unset or unrecognized */
+  provider: TranslationProvider | null;
 }
 
-export async function translateText(
-  text: string,
-  targetLang: string,
+const MAX_RETRIES = 2; // Retries for 429 and 5xx responses
+const MAX_RETRY_DELAY = 30000; // Never wait longer than 30s between attempts
+export const DEFAULT_TARGET_LANG = "zh";
+
+/**
+ * Language to translate into when a request doesn't name one, from the
+ * default_target_lang setting
+ */
+export function getDefaultTargetLang(db: Database): string {
+  return getSettingOr(db, "default_target_lang", DEFAULT_TARGET_LANG).trim();
+}
+
+/**
+ * Load translation settings from the settings table
+ */
+export function getTranslationSettings(db: Database): TranslationSettings {
+  return {
+    baseUrl: getSettingOr(
+      db,
+      "translation_base_url",
+      "https://libretranslate.com",
+    ),
+    apiKey: getSettingOr(db, "translation_api_key", ""),
+    model: getSettingOr(db, "translation_model", "gpt-3.5-turbo"),
+    prompt: getSettingOr(
+      db,
+      "translation_prompt",
+      "Translate the following text to Chinese:",
+    ),
+    timeoutSeconds: getIntSetting(db, "translation_timeout_seconds", 60, 1),
+    provider: parseProvider(getSettingOr(db, "translation_provider", "")),
+  };
+}
+
+/**
+ * POST to a translation provider with a timeout, retrying rate-limited and
+ * server errors while honoring Retry-After
+ */
+async function fetchWithRetry(
+  url: string,
+  init: RequestInit,
+  timeoutSeconds: number,
+): Promise<Response> {
+  for (let attempt = 0; ; attempt++) {
+    const controller = new AbortController();
+    const timeoutId = setTimeout(
+      () => controller.abort(),
+      timeoutSeconds * 1000,
+    );
+
+    let response: Response;
+    try {
+      response = await fetch(url, { ...init, signal: controller.signal });
+    } catch (error: any) {
+      if (error.name === "AbortError") {
+        throw new Error(
+          `Translation request timed out after ${timeoutSeconds}s. Check the provider URL or increase translation_timeout_seconds.`,
+        );
+      }
+      throw error;
+    } finally {
+      clearTimeout(timeoutId);
+    }
+
+    const retryable = response.status === 429 || response.status >= 500;
+    if (!retryable || attempt >= MAX_RETRIES) {
+      return response;
+    }
+
+    const retryAfter = parseRetryAfter(response.headers.get("retry-after"));
+    const delay = Math.min(retryAfter ?? 1000 * 2 ** attempt, MAX_RETRY_DELAY);
+    console.warn(
+      `[Translate] Provider returned ${response.status}, retry in ${delay}ms`,
+    );
+    await response.body?.cancel();
+    await sleep(delay);
+  }
+}
+
+export type TranslationProvider = "openai" | "libretranslate" | "deepl";
+
+const TRANSLATION_PROVIDERS: TranslationProvider[] = [
+  "openai",
+  "libretranslate",
+  "deepl",
+];
+
+function parseProvider(value: string): TranslationProvider | null {
+  const provider = value.trim().toLowerCase() as TranslationProvider;
+  if (!provider) return null;
+
+  if (!TRANSLATION_PROVIDERS.includes(provider)) {
+    console.warn(`[Translate] Unknown translation_provider '${value}'`);
+    return null;
+  }
+  return provider;
+}
+
+export interface TranslationConfigSummary {
+  provider: TranslationProvider;
+  /** Model name, only meaningful for OpenAI-compatible providers */
+  model: string | null;
+  baseUrlHost: string | null;
+  hasApiKey: boolean;
+}
+
+/**
+ * The provider to use: the translation_provider setting, or for configs
+ * saved before that setting existed, a guess from the URL and API key
+ */
+export function detectProvider(
   settings: TranslationSettings,
-): Promise<string> {
-  const { baseUrl, apiKey, model, prompt } = settings;
+): TranslationProvider {
+  const { baseUrl, apiKey } = settings;
+
+  if (settings.provider) {
+    return settings.provider;
+  }
 
-  // Determine if this is OpenAI API or LibreTranslate
   const isOpenAI =
     baseUrl.includes("openai.com") ||
     baseUrl.includes("openai") ||
@@ -20,42 +139,363 @@ export async function translateText(
     baseUrl.endsWith("/v1") ||
     (apiKey && !baseUrl.includes("libretranslate"));
 
-  if (isOpenAI) {
-    return translateWithOpenAI(text, baseUrl, apiKey, model, prompt);
+  return isOpenAI ? "openai" : "libretranslate";
+}
+
+/**
+ * Describe the effective translation config without exposing the API key
+ */
+export function getTranslationConfigSummary(
+  settings: TranslationSettings,
+): TranslationConfigSummary {
+  const provider = detectProvider(settings);
+
+  let baseUrlHost: string | null = null;
+  try {
+    baseUrlHost = new URL(
+      provider === "deepl" ? deepLApiUrl(settings.apiKey) : settings.baseUrl,
+    ).host;
+  } catch {
+    // Leave host empty for malformed URLs
+  }
+
+  return {
+    provider,
+    model: provider === "openai" ? settings.model : null,
+    baseUrlHost,
+    hasApiKey: settings.apiKey.length > 0,
+  };
+}
+
+export async function translateText(
+  text: string,
+  targetLang: string,
+  settings: TranslationSettings,
+): Promise<string> {
+  const { baseUrl, apiKey, model, prompt, timeoutSeconds } = settings;
+  const provider = detectProvider(settings);
+
+  if (provider === "deepl") {
+    return translateWithDeepL(text, targetLang, apiKey, timeoutSeconds);
+  } else if (provider === "openai") {
+    return translateWithOpenAI(
+      text,
+      targetLang,
+      baseUrl,
+      apiKey,
+      model,
+      prompt,
+      timeoutSeconds,
+    );
   } else {
-    return translateWithLibre(text, targetLang, baseUrl, apiKey);
+    return translateWithLibre(
+      text,
+      targetLang,
+      baseUrl,
+      apiKey,
+      timeoutSeconds,
+    );
+  }
+}
+
+/**
+ * translateTextCached that yields the translation piece by piece as an
+ * OpenAI-compatible provider streams it. Other providers, and text already
+ * in the cache, yield the whole translation at once
+ */
+export async function* translateTextStreamCached(
+  db: Database,
+  text: string,
+  targetLang: string,
+  settings: TranslationSettings,
+): AsyncGenerator<string> {
+  const cached = getCachedTranslation(db, text, targetLang);
+  if (cached !== null) {
+    yield cached;
+    return;
   }
+
+  if (detectProvider(settings) !== "openai") {
+    const translated = await translateText(text, targetLang, settings);
+    cacheTranslation(db, text, targetLang, translated);
+    yield translated;
+    return;
+  }
+
+  let translated = "";
+  for await (const piece of streamWithOpenAI(text, targetLang, settings)) {
+    translated += piece;
+    yield piece;
+  }
+  cacheTranslation(db, text, targetLang, translated);
+}
+
+/**
+ * translateWithOpenAI with "stream": true, yielding each content delta from
+ * the server-sent events as it arrives
+ */
+async function* streamWithOpenAI(
+  text: string,
+  targetLang: string,
+  settings: TranslationSettings,
+): AsyncGenerator<string> {
+  const { baseUrl, apiKey, model, prompt, timeoutSeconds } = settings;
+  const response = await fetchWithRetry(
+    `${baseUrl.replace(/\/$/, "")}/chat/completions`,
+    {
+      method: "POST",
+      headers: {
+        "Content-Type": "application/json",
+        Authorization: `Bearer ${apiKey}`,
+      },
+      body: JSON.stringify({
+        model,
+        messages: [
+          { role: "system", content: systemPrompt(prompt, targetLang) },
+          { role: "user", content: text },
+        ],
+        temperature: 0.3,
+        stream: true,
+      }),
+    },
+    timeoutSeconds,
+  );
+
+  if (!response.ok || !response.body) {
+    const error = await response.text();
+    throw new Error(`OpenAI API error (${response.status}): ${error}`);
+  }
+
+  const reader = response.body.pipeThrough(new TextDecoderStream()).getReader();
+  let buffer = "";
+  for (;;) {
+    const { done, value } = await reader.read();
+    if (done) return;
+
+    // Events are "data: <json>" lines; keep a partial last line for later
+    buffer += value;
+    const lines = buffer.split("\n");
+    buffer = lines.pop()!;
+
+    for (const line of lines) {
+      if (!line.startsWith("data:")) continue;
+      const data = line.slice(5).trim();
+      if (data === "[DONE]") {
+        await reader.cancel();
+        return;
+      }
+      const delta = JSON.parse(data).choices?.[0]?.delta?.content;
+      if (delta) yield delta;
+    }
+  }
+}
+
+/**
+ * Cache key for a translation request
+ */
+function translationHash(text: string, targetLang: string): string {
+  return createHash("sha256").update(`${targetLang}\0${text}`).digest("hex");
+}
+
+/**
+ * translateText backed by the translation_cache table, so identical text is
+ * only sent to the provider once per target language
+ */
+export async function translateTextCached(
+  db: Database,
+  text: string,
+  targetLang: string,
+  settings: TranslationSettings,
+): Promise<string> {
+  const cached = getCachedTranslation(db, text, targetLang);
+  if (cached !== null) {
+    return cached;
+  }
+
+  const translated = await translateText(text, targetLang, settings);
+  cacheTranslation(db, text, targetLang, translated);
+
+  return translated;
+}
+
+function getCachedTranslation(
+  db: Database,
+  text: string,
+  targetLang: string,
+): string | null {
+  const cached = db
+    .query("SELECT content FROM translation_cache WHERE hash = ?")
+    .get(translationHash(text, targetLang)) as { content: string } | null;
+  return cached?.content ?? null;
+}
+
+function cacheTranslation(
+  db: Database,
+  text: string,
+  targetLang: string,
+  translated: string,
+): void {
+  db.query(
+    `INSERT OR REPLACE INTO translation_cache (hash, target_lang, content)
+     VALUES (?, ?, ?)`,
+  ).run(translationHash(text, targetLang), targetLang, translated);
+}
+
+const BATCH_TRANSLATE_SIZE = 20; // Texts per coalesced OpenAI request
+
+/**
+ * Translate many short texts, consulting the cache first
+ * OpenAI-compatible providers get cache misses coalesced into one request
+ * per batch; other providers are called once per text.
+ * @returns Translations in the same order as texts
+ */
+export async function translateTextsCached(
+  db: Database,
+  texts: string[],
+  targetLang: string,
+  settings: TranslationSettings,
+): Promise<string[]> {
+  const results = texts.map((text) =>
+    getCachedTranslation(db, text, targetLang),
+  );
+  const misses = [...new Set(texts.filter((_, i) => results[i] === null))];
+
+  const translated = new Map<string, string>();
+  if (detectProvider(settings) === "openai") {
+    for (let i = 0; i < misses.length; i += BATCH_TRANSLATE_SIZE) {
+      const batch = misses.slice(i, i + BATCH_TRANSLATE_SIZE);
+      const output = await translateBatchWithOpenAI(
+        batch,
+        targetLang,
+        settings,
+      );
+      batch.forEach((text, j) => translated.set(text, output[j]));
+    }
+  } else {
+    for (const text of misses) {
+      translated.set(text, await translateText(text, targetLang, settings));
+    }
+  }
+
+  for (const [text, value] of translated) {
+    cacheTranslation(db, text, targetLang, value);
+  }
+
+  return texts.map((text, i) => results[i] ?? translated.get(text)!);
+}
+
+/**
+ * Translate several texts in one chat completion, exchanged as JSON arrays
+ * Falls back to one request per text if the reply is not a matching array
+ */
+async function translateBatchWithOpenAI(
+  texts: string[],
+  targetLang: string,
+  settings: TranslationSettings,
+): Promise<string[]> {
+  if (texts.length === 1) {
+    return [await translateText(texts[0], targetLang, settings)];
+  }
+
+  const { baseUrl, apiKey, model, timeoutSeconds } = settings;
+  const response = await fetchWithRetry(
+    `${baseUrl.replace(/\/$/, "")}/chat/completions`,
+    {
+      method: "POST",
+      headers: {
+        "Content-Type": "application/json",
+        Authorization: `Bearer ${apiKey}`,
+      },
+      body: JSON.stringify({
+        model,
+        messages: [
+          {
+            role: "system",
+            content: `You are a professional translator. The user sends a JSON array of strings. Translate each string to the language with code "${targetLang}" and reply with only a JSON array of the translations, in the same order and with the same length.`,
+          },
+          { role: "user", content: JSON.stringify(texts) },
+        ],
+        temperature: 0.3,
+      }),
+    },
+    timeoutSeconds,
+  );
+
+  if (!response.ok) {
+    const error = await response.text();
+    throw new Error(`OpenAI API error (${response.status}): ${error}`);
+  }
+
+  const json = await response.json();
+  const reply: string = json.choices[0]?.message?.content || "";
+
+  try {
+    // Models sometimes wrap the array in a code fence
+    const parsed = JSON.parse(reply.replace(/^```\w*\s*|\s*```$/g, ""));
+    if (
+      Array.isArray(parsed) &&
+      parsed.length === texts.length &&
+      parsed.every((item) => typeof item === "string")
+    ) {
+      return parsed;
+    }
+  } catch {
+    // Handled by the fallback below
+  }
+
+  console.warn("[Translate] Batch reply was not a matching array, retrying");
+  const results: string[] = [];
+  for (const text of texts) {
+    results.push(await translateText(text, targetLang, settings));
+  }
+  return results;
+}
+
+const MARKDOWN_PROMPT =
+  "You are a professional translator. Translate the following Markdown text while preserving all Markdown formatting (links, images, code blocks, etc.). Only translate the readable text content, keep URLs and Markdown syntax unchanged.";
+
+/**
+ * The configured prompt (or MARKDOWN_PROMPT) plus the target language. The
+ * language goes last so it wins over one named in an older prompt, such as
+ * the "to Chinese" default
+ */
+function systemPrompt(prompt: string, targetLang: string): string {
+  return `${prompt || MARKDOWN_PROMPT}\n\nTranslate into the language with code "${targetLang}", whatever language is named above.`;
 }
 
 async function translateWithOpenAI(
   text: string,
+  targetLang: string,
   baseUrl: string,
   apiKey: string,
   model: string,
   prompt: string,
+  timeoutSeconds: number,
 ): Promise<string> {
   const apiUrl = `${baseUrl.replace(/\/$/, "")}/chat/completions`;
 
   // Enhanced prompt for Markdown translation
-  const markdownPrompt =
-    prompt ||
-    "You are a professional translator. Translate the following Markdown text while preserving all Markdown formatting (links, images, code blocks, etc.). Only translate the readable text content, keep URLs and Markdown syntax unchanged.";
-
-  const response = await fetch(apiUrl, {
-    method: "POST",
-    headers: {
-      "Content-Type": "application/json",
-      Authorization: `Bearer ${apiKey}`,
+  const markdownPrompt = systemPrompt(prompt, targetLang);
+
+  const response = await fetchWithRetry(
+    apiUrl,
+    {
+      method: "POST",
+      headers: {
+        "Content-Type": "application/json",
+        Authorization: `Bearer ${apiKey}`,
+      },
+      body: JSON.stringify({
+        model,
+        messages: [
+          { role: "system", content: markdownPrompt },
+          { role: "user", content: text },
+        ],
+        temperature: 0.3,
+      }),
     },
-    body: JSON.stringify({
-      model,
-      messages: [
-        { role: "system", content: markdownPrompt },
-        { role: "user", content: text },
-      ],
-      temperature: 0.3,
-    }),
-  });
+    timeoutSeconds,
+  );
 
   if (!response.ok) {
     const error = await response.text();
@@ -71,6 +511,7 @@ async function translateWithLibre(
   targetLang: string,
   baseUrl: string,
   apiKey: string,
+  timeoutSeconds: number,
 ): Promise<string> {
   const translateUrl = `${baseUrl.replace(/\/$/, "")}/translate`;
 
@@ -85,11 +526,15 @@ async function translateWithLibre(
     body.api_key = apiKey;
   }
 
-  const response = await fetch(translateUrl, {
-    method: "POST",
-    headers: { "Content-Type": "application/json" },
-    body: JSON.stringify(body),
-  });
+  const response = await fetchWithRetry(
+    translateUrl,
+    {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify(body),
+    },
+    timeoutSeconds,
+  );
 
   if (!response.ok) {
     const error = await response.text();
@@ -99,3 +544,56 @@ async function translateWithLibre(
   const json = await response.json();
   return json.translatedText || "";
 }
+
+/**
+ * DeepL endpoint for a key; free-plan keys end in ":fx" and are only
+ * accepted by the free endpoint
+ */
+function deepLApiUrl(apiKey: string): string {
+  return apiKey.endsWith(":fx")
+    ? "https://api-free.deepl.com/v2/translate"
+    : "https://api.deepl.com/v2/translate";
+}
+
+/**
+ * DeepL target language code: upper-case, and English needs a variant
+ */
+function toDeepLLang(lang: string): string {
+  const code = lang.toUpperCase();
+  return code === "EN" ? "EN-US" : code;
+}
+
+async function translateWithDeepL(
+  text: string,
+  targetLang: string,
+  apiKey: string,
+  timeoutSeconds: number,
+): Promise<string> {
+  if (!apiKey) {
+    throw new Error("DeepL requires an API key (translation_api_key)");
+  }
+
+  const response = await fetchWithRetry(
+    deepLApiUrl(apiKey),
+    {
+      method: "POST",
+      headers: {
+        "Content-Type": "application/x-www-form-urlencoded",
+        Authorization: `DeepL-Auth-Key ${apiKey}`,
+      },
+      body: new URLSearchParams({
+        text,
+        target_lang: toDeepLLang(targetLang),
+      }),
+    },
+    timeoutSeconds,
+  );
+
+  if (!response.ok) {
+    const error = await response.text();
+    throw new Error(`DeepL API error (${response.status}): ${error}`);
+  }
+
+  const json = await response.json();
+  return json.translations?.[0]?.text || "";
+}
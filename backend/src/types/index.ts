@@ -1,10 +1,33 @@
 export interface Feed {
   id: string;
   title: string;
+  /** Title translated by translate-titles; the original title is untouched */
+  titleTranslated?: string;
   url: string;
   description?: string;
   imageUrl?: string;
   category?: string;
+  /** Use the entry summary instead of full content when both exist */
+  preferSummary?: boolean;
+  /** Extra request headers sent when fetching this feed */
+  headers?: Record<string, string>;
+  /** Message from the last failed refresh, cleared on success */
+  lastError?: string;
+  /** When the feed was last fetched successfully */
+  lastFetchedAt?: number;
+  /** Duration of the last fetch, when record_fetch_timing is on */
+  lastFetchMs?: number | null;
+  /** Language tag from the feed, or detected from its articles */
+  language?: string;
+  /** Auto-refresh interval; null uses auto_refresh_interval_minutes */
+  refreshIntervalMinutes?: number | null;
+  /** Position set by reorder; unordered feeds (null) sort first by title */
+  sortOrder?: number | null;
+  /** HTTP basic auth user; the password itself is never returned */
+  username?: string | null;
+  hasPassword?: boolean;
+  /** New articles are saved as already read */
+  autoMarkRead?: boolean;
   createdAt: number;
   updatedAt: number;
 }
@@ -18,9 +41,28 @@ export interface Article {
   summary?: string;
   author?: string;
   pubDate?: number;
-  isRead: number;
-  isStarred: number;
+  /** Date string as written in the feed, before parsing */
+  pubDateRaw?: string | null;
+  isRead: boolean;
+  isStarred: boolean;
+  /**
+   * Kept articles are never removed by retention or cleanup policies.
+   * Unlike starring, which users also toggle as a bookmark, keeping is an
+   * explicit "archive forever" guarantee.
+   */
+  isKept: boolean;
+  readAt?: number;
+  /** Attached media such as a podcast episode */
+  enclosureUrl?: string;
+  enclosureType?: string;
+  enclosureLength?: number;
+  /** Lead image used as the list thumbnail */
+  imageUrl?: string;
+  /** Words in the article text, for reading time estimates */
+  wordCount?: number;
   fetchedAt: number;
+  /** When an edit at the source was last applied (update_existing_articles) */
+  updatedAt?: number | null;
 }
 
 export interface Translation {
@@ -29,4 +71,114 @@ export interface Translation {
   createdAt: number;
 }
 
+export interface FeedResponseInfo {
+  finalUrl: string;
+  redirected: boolean;
+  status: number;
+  statusText: string;
+  contentType: string | null;
+  contentLength: number | null;
+  etag: string | null;
+  lastModified: string | null;
+  cacheControl: string | null;
+}
+
+/** What a feed URL contains, fetched without subscribing to it */
+export interface FeedPreview {
+  /** URL the feed was finally served from, after redirects */
+  url: string;
+  title: string | null;
+  description: string | null;
+  siteUrl: string | null;
+  articleCount: number;
+}
+
+export interface HostStat {
+  host: string;
+  count: number;
+  feedIds: string[];
+  /** Enough feeds on this host that refresh-all may get rate limited */
+  rateLimitRisk: boolean;
+}
+
+export interface Stats {
+  totalFeeds: number;
+  totalArticles: number;
+  unreadCount: number;
+  starredCount: number;
+  /** Articles fetched in the last 24 hours */
+  fetchedLast24h: number;
+}
+
+/** Outcome of refreshing one feed during refresh-all */
+export interface RefreshResult {
+  feedId: string;
+  title: string;
+  newCount: number;
+  /** Ids of the articles saved by this refresh */
+  newIds: string[];
+  /** Stored articles updated after an edit at the source */
+  updated: number;
+  /** Malformed entries left out of the feed */
+  skipped: number;
+  error: string | null;
+}
+
+export interface DatabaseOptimization {
+  /** "ok", or the problems PRAGMA integrity_check reported */
+  integrity: string;
+  /** Database plus WAL file size in bytes */
+  sizeBefore: number;
+  sizeAfter: number;
+}
+
+export interface FeedHealth {
+  feedId: string;
+  title: string;
+  url: string;
+  /** HTTP status of the final response; null when no response arrived */
+  status: number | null;
+  /** The body is a valid RSS, Atom or JSON feed */
+  parses: boolean;
+  articleCount: number;
+  /** Newest entry date in the feed, unix seconds */
+  lastPubDate: number | null;
+  error: string | null;
+}
+
+export interface UnreadCount {
+  feedId: string;
+  count: number;
+}
+
+export interface FeedGroup {
+  /** The category, or "Uncategorized" for feeds without one */
+  name: string;
+  /** Unread articles across every feed in the group */
+  unreadCount: number;
+  feeds: (Feed & { unreadCount: number })[];
+}
+
+export type FilterRuleField = "title" | "content" | "any";
+
+export type FilterRuleAction = "mark_read" | "star" | "delete";
+
+/** Keyword rule applied to newly fetched articles */
+export interface FilterRule {
+  id: string;
+  /** Case-insensitive text to look for */
+  pattern: string;
+  field: FilterRuleField;
+  action: FilterRuleAction;
+  /** Limits the rule to one feed; null applies it to every feed */
+  feedId: string | null;
+  createdAt: number;
+}
+
 export type ArticleFilter = "all" | "unread" | "starred";
+
+export type ArticleSort =
+  | "date_desc"
+  | "date_asc"
+  | "fetched_desc"
+  | "title_asc";
@@ -0,0 +1,49 @@
+import { describe, it, expect } from "bun:test";
+import { decodeFeedBytes, detectCharset } from "@/utils/charset";
+
+const ascii = (text: string) => new TextEncoder().encode(text);
+
+describe("detectCharset", () => {
+  it("prefers the Content-Type charset", () => {
+    const bytes = ascii('<?xml version="1.0" encoding="UTF-8"?><rss/>');
+    expect(detectCharset(bytes, "text/xml; charset=ISO-8859-1")).toBe(
+      "iso-8859-1",
+    );
+  });
+
+  it("reads the XML declaration", () => {
+    const bytes = ascii("<?xml version='1.0' encoding='GB2312'?><rss/>");
+    expect(detectCharset(bytes, "application/rss+xml")).toBe("gb2312");
+  });
+
+  it("lets a byte order mark win", () => {
+    const bytes = new Uint8Array([0xef, 0xbb, 0xbf, ...ascii("<rss/>")]);
+    expect(detectCharset(bytes, "text/xml; charset=latin1")).toBe("utf-8");
+  });
+
+  it("returns null when nothing is declared", () => {
+    expect(detectCharset(ascii("<rss/>"), "text/xml")).toBeNull();
+  });
+});
+
+describe("decodeFeedBytes", () => {
+  it("decodes ISO-8859-1 bodies", () => {
+    // "café" with é as the single byte 0xe9
+    const bytes = new Uint8Array([...ascii("caf"), 0xe9]);
+    expect(decodeFeedBytes(bytes, "text/xml; charset=ISO-8859-1")).toBe(
+      "café",
+    );
+  });
+
+  it("defaults to UTF-8", () => {
+    expect(decodeFeedBytes(ascii("<title>café</title>"))).toBe(
+      "<title>café</title>",
+    );
+  });
+
+  it("falls back to UTF-8 for unknown encodings", () => {
+    expect(decodeFeedBytes(ascii("<rss/>"), "text/xml; charset=bogus")).toBe(
+      "<rss/>",
+    );
+  });
+});
@@ -0,0 +1,55 @@
+// How far into the body to look for an XML encoding declaration
+const DECLARATION_SCAN_BYTES = 1024;
+
+/**
+ * Find the character encoding of a feed body: a byte order mark, then the
+ * Content-Type charset, then the XML declaration
+ * @returns The encoding label, or null when none is declared
+ */
+export function detectCharset(
+  bytes: Uint8Array,
+  contentType?: string | null,
+): string | null {
+  if (bytes[0] === 0xef && bytes[1] === 0xbb && bytes[2] === 0xbf) {
+    return "utf-8";
+  }
+  if (bytes[0] === 0xff && bytes[1] === 0xfe) {
+    return "utf-16le";
+  }
+  if (bytes[0] === 0xfe && bytes[1] === 0xff) {
+    return "utf-16be";
+  }
+
+  const headerCharset = contentType?.match(/charset\s*=\s*"?([\w.:-]+)/i);
+  if (headerCharset) {
+    return headerCharset[1].toLowerCase();
+  }
+
+  // The declaration itself is ASCII in every encoding we can decode
+  const head = new TextDecoder("latin1").decode(
+    bytes.subarray(0, DECLARATION_SCAN_BYTES),
+  );
+  const declared = head.match(/^\s*<\?xml[^>]*encoding\s*=\s*["']([\w.:-]+)/i);
+  return declared ? declared[1].toLowerCase() : null;
+}
+
+/**
+ * Decode a feed body using its detected charset
+ * Unknown or unsupported encodings fall back to lossy UTF-8
+ */
+export function decodeFeedBytes(
+  bytes: Uint8Array,
+  contentType?: string | null,
+): string {
+  const charset = detectCharset(bytes, contentType);
+
+  if (charset) {
+    try {
+      return new TextDecoder(charset).decode(bytes);
+    } catch {
+      // RangeError for labels the runtime does not support
+    }
+  }
+
+  return new TextDecoder().decode(bytes);
+}
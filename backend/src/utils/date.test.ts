@@ -0,0 +1,33 @@
+import { describe, it, expect } from "bun:test";
+import { parseLooseDate } from "@/utils/date";
+
+const at = (iso: string) => Date.parse(iso) / 1000;
+
+describe("parseLooseDate", () => {
+  it("parses loose RFC 822 dates", () => {
+    expect(parseLooseDate("Tue, 5 Mar 2024 10:00:00 +0100")).toBe(
+      at("2024-03-05T09:00:00Z"),
+    );
+    expect(parseLooseDate("Thursday, 05 Sept 2024 10:00 CEST")).toBe(
+      at("2024-09-05T08:00:00Z"),
+    );
+    expect(parseLooseDate("05-Mar-24")).toBe(at("2024-03-05T00:00:00Z"));
+  });
+
+  it("parses SQL-style and bare dates", () => {
+    expect(parseLooseDate("2024-03-05 10:30:15")).toBe(
+      at("2024-03-05T10:30:15Z"),
+    );
+    expect(parseLooseDate("2024/03/05 10:30 -05:00")).toBe(
+      at("2024-03-05T15:30:00Z"),
+    );
+    expect(parseLooseDate("2024-03-05")).toBe(at("2024-03-05T00:00:00Z"));
+  });
+
+  it("returns null for text that is not a date", () => {
+    expect(parseLooseDate("yesterday")).toBeNull();
+    expect(parseLooseDate("2024-02-30")).toBeNull();
+    expect(parseLooseDate("5 Foo 2024")).toBeNull();
+    expect(parseLooseDate("2024-03-05 10:00 and more")).toBeNull();
+  });
+});
@@ -0,0 +1,143 @@
+const MONTHS = [
+  "jan",
+  "feb",
+  "mar",
+  "apr",
+  "may",
+  "jun",
+  "jul",
+  "aug",
+  "sep",
+  "oct",
+  "nov",
+  "dec",
+];
+
+// Zone names seen in feeds, as minutes east of UTC
+const TIMEZONES: Record<string, number> = {
+  UT: 0,
+  UTC: 0,
+  GMT: 0,
+  Z: 0,
+  EST: -300,
+  EDT: -240,
+  CST: -360,
+  CDT: -300,
+  MST: -420,
+  MDT: -360,
+  PST: -480,
+  PDT: -420,
+  BST: 60,
+  CET: 60,
+  CEST: 120,
+  EET: 120,
+  EEST: 180,
+  MSK: 180,
+  JST: 540,
+  KST: 540,
+  AEST: 600,
+  AEDT: 660,
+};
+
+// "Tue, 5 Mar 2024 10:00 CEST", "05-Sept-24", "Mon 5 March 2024 10:00:00"
+const RFC822_LIKE =
+  /^(?:[a-z]+\.?,? )?(\d{1,2})[ -]([a-z]{3})[a-z]*\.?,?[ -](\d{2}|\d{4})(?:,? (\d{1,2}):(\d{2})(?::(\d{2}))?)?(?: (.+))?$/i;
+
+// "2024-03-05 10:00:00", "2024/03/05 10:00 +0100", "2024-03-05"
+const ISO_LIKE =
+  /^(\d{4})[-/.](\d{1,2})[-/.](\d{1,2})(?:[ T](\d{1,2}):(\d{2})(?::(\d{2})(?:\.\d+)?)?)?(?: ?(.+))?$/i;
+
+/**
+ * Parse the date formats feeds use that the feed parser rejects: loose
+ * RFC 822 variants, "YYYY-MM-DD HH:MM:SS" and bare dates. Dates without a
+ * zone are taken as UTC, unknown zone names are ignored.
+ * @returns Unix timestamp in seconds, or null when nothing matches
+ */
+export function parseLooseDate(raw: string): number | null {
+  const value = raw.trim().replace(/\s+/g, " ");
+
+  const rfc = value.match(RFC822_LIKE);
+  if (rfc) {
+    const month = MONTHS.indexOf(rfc[2].toLowerCase());
+    let year = Number(rfc[3]);
+    if (rfc[3].length === 2) {
+      year += year < 50 ? 2000 : 1900;
+    }
+    return toTimestamp(
+      [year, month, Number(rfc[1]), rfc[4], rfc[5], rfc[6]],
+      rfc[7],
+    );
+  }
+
+  const iso = value.match(ISO_LIKE);
+  if (iso) {
+    return toTimestamp(
+      [
+        Number(iso[1]),
+        Number(iso[2]) - 1,
+        Number(iso[3]),
+        iso[4],
+        iso[5],
+        iso[6],
+      ],
+      iso[7],
+    );
+  }
+
+  return null;
+}
+
+function toTimestamp(
+  [year, month, day, hours, minutes, seconds]: [
+    number,
+    number,
+    number,
+    string?,
+    string?,
+    string?,
+  ],
+  zone: string | undefined,
+): number | null {
+  const offset = parseZone(zone);
+  if (month < 0 || offset === null) {
+    return null;
+  }
+
+  const time = Date.UTC(
+    year,
+    month,
+    day,
+    Number(hours ?? 0),
+    Number(minutes ?? 0),
+    Number(seconds ?? 0),
+  );
+  // Date.UTC rolls overflowing fields forward; "Feb 30" is not a date
+  const date = new Date(time);
+  if (date.getUTCMonth() !== month || date.getUTCDate() !== day) {
+    return null;
+  }
+
+  return Math.floor((time - offset * 60_000) / 1000);
+}
+
+/**
+ * Minutes east of UTC for "+0100", "-05:00" or a zone name
+ * @returns null when the trailing text is not a zone at all
+ */
+function parseZone(zone: string | undefined): number | null {
+  if (!zone) {
+    return 0;
+  }
+
+  const offset = zone.match(/^([+-])(\d{2}):?(\d{2})$/);
+  if (offset) {
+    const minutes = Number(offset[2]) * 60 + Number(offset[3]);
+    return offset[1] === "-" ? -minutes : minutes;
+  }
+
+  if (/^[a-z]{1,5}$/i.test(zone)) {
+    return TIMEZONES[zone.toUpperCase()] ?? 0;
+  }
+
+  return null;
+}
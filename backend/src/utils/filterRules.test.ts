@@ -0,0 +1,61 @@
+import { describe, it, expect } from "bun:test";
+import { evaluateFilterRules } from "@/utils/filterRules";
+
+const article = {
+  title: "Weekly Sponsored Roundup",
+  content: "<p>Big news about <b>Rust</b> releases</p>",
+};
+
+describe("evaluateFilterRules", () => {
+  it("matches titles case-insensitively", () => {
+    const outcome = evaluateFilterRules(
+      [{ pattern: "sponsored", field: "title", action: "mark_read" }],
+      article,
+    );
+
+    expect(outcome).toEqual({ read: true, starred: false, remove: false });
+  });
+
+  it("matches content text rather than markup", () => {
+    const rules = [
+      { pattern: "rust", field: "content", action: "star" },
+      { pattern: "<b>", field: "content", action: "delete" },
+    ] as const;
+
+    expect(evaluateFilterRules([...rules], article)).toEqual({
+      read: false,
+      starred: true,
+      remove: false,
+    });
+  });
+
+  it("only checks the configured field", () => {
+    const outcome = evaluateFilterRules(
+      [{ pattern: "rust", field: "title", action: "delete" }],
+      article,
+    );
+
+    expect(outcome.remove).toBe(false);
+  });
+
+  it("combines the actions of every matching rule", () => {
+    const outcome = evaluateFilterRules(
+      [
+        { pattern: "roundup", field: "any", action: "mark_read" },
+        { pattern: "releases", field: "any", action: "star" },
+      ],
+      article,
+    );
+
+    expect(outcome).toEqual({ read: true, starred: true, remove: false });
+  });
+
+  it("ignores blank patterns", () => {
+    const outcome = evaluateFilterRules(
+      [{ pattern: "  ", field: "any", action: "delete" }],
+      article,
+    );
+
+    expect(outcome.remove).toBe(false);
+  });
+});
@@ -0,0 +1,61 @@
+import type { FilterRule, FilterRuleAction, FilterRuleField } from "@/types";
+import { htmlToText } from "@/utils/text";
+
+export const FILTER_RULE_FIELDS: FilterRuleField[] = [
+  "title",
+  "content",
+  "any",
+];
+
+export const FILTER_RULE_ACTIONS: FilterRuleAction[] = [
+  "mark_read",
+  "star",
+  "delete",
+];
+
+export interface FilterableArticle {
+  title: string;
+  content: string;
+}
+
+export interface FilterOutcome {
+  read: boolean;
+  starred: boolean;
+  /** The article should not be stored at all */
+  remove: boolean;
+}
+
+/**
+ * Work out what the given rules do to an article. Patterns match
+ * case-insensitively anywhere in the title and/or the text of the content.
+ */
+export function evaluateFilterRules(
+  rules: Pick<FilterRule, "pattern" | "field" | "action">[],
+  article: FilterableArticle,
+): FilterOutcome {
+  const outcome = { read: false, starred: false, remove: false };
+  const title = article.title.toLowerCase();
+  // Content is only converted to text when a rule needs it
+  let content: string | null = null;
+
+  for (const rule of rules) {
+    const pattern = rule.pattern.trim().toLowerCase();
+    if (!pattern) continue;
+
+    let matched = false;
+    if (rule.field !== "content") {
+      matched = title.includes(pattern);
+    }
+    if (!matched && rule.field !== "title") {
+      content ??= htmlToText(article.content || "").toLowerCase();
+      matched = content.includes(pattern);
+    }
+    if (!matched) continue;
+
+    if (rule.action === "mark_read") outcome.read = true;
+    else if (rule.action === "star") outcome.starred = true;
+    else if (rule.action === "delete") outcome.remove = true;
+  }
+
+  return outcome;
+}
@@ -0,0 +1,44 @@
+import { describe, it, expect } from "bun:test";
+import { highlightKeywords } from "@/utils/highlight";
+
+describe("highlightKeywords", () => {
+  it("marks matches in plain text, ignoring case", () => {
+    expect(highlightKeywords("Rust and rust", ["RUST"])).toBe(
+      "<mark>Rust</mark> and <mark>rust</mark>",
+    );
+  });
+
+  it("leaves link targets alone but marks the link text", () => {
+    expect(
+      highlightKeywords("See [rust news](https://rust.example/rust)", ["rust"]),
+    ).toBe("See [<mark>rust</mark> news](https://rust.example/rust)");
+  });
+
+  it("leaves inline code and code blocks alone", () => {
+    expect(highlightKeywords("Use `cargo rust` in rust", ["rust"])).toBe(
+      "Use `cargo rust` in <mark>rust</mark>",
+    );
+    expect(highlightKeywords("```\nrust\n```\nrust", ["rust"])).toBe(
+      "```\nrust\n```\n<mark>rust</mark>",
+    );
+  });
+
+  it("leaves HTML tags alone", () => {
+    expect(
+      highlightKeywords('<span class="rust">rust</span>', ["rust", "span"]),
+    ).toBe('<span class="rust"><mark>rust</mark></span>');
+  });
+
+  it("prefers the longest keyword when keywords overlap", () => {
+    expect(highlightKeywords("rustacean rust", ["rust", "rustacean"])).toBe(
+      "<mark>rustacean</mark> <mark>rust</mark>",
+    );
+  });
+
+  it("ignores blank keywords and escapes regex characters", () => {
+    expect(highlightKeywords("C++ and C", [" ", "C++"])).toBe(
+      "<mark>C++</mark> and C",
+    );
+    expect(highlightKeywords("text", [])).toBe("text");
+  });
+});
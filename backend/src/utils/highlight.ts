@@ -0,0 +1,44 @@
+// Markdown spans that must not be touched: code, images, link targets,
+// autolinks/HTML tags and bare URLs
+const PROTECTED_PATTERN =
+  /```[\s\S]*?```|`[^`\n]*`|!\[[^\]]*\]\([^)]*\)|\]\([^)]*\)|<[^>\n]*>|https?:\/\/\S+/g;
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
+
+/**
+ * Wrap case-insensitive keyword matches in <mark> tags
+ * Only plain text is touched, so links, code and markup stay intact
+ * @param markdown Article content in Markdown format
+ * @param keywords Keywords to highlight
+ * @returns Markdown with <mark> tags around matches
+ */
+export function highlightKeywords(
+  markdown: string,
+  keywords: string[],
+): string {
+  const terms = keywords
+    .map((keyword) => keyword.trim())
+    .filter(Boolean)
+    // Prefer the longest match when keywords overlap
+    .sort((a, b) => b.length - a.length);
+
+  if (!markdown || terms.length === 0) {
+    return markdown;
+  }
+
+  const pattern = new RegExp(`(${terms.map(escapeRegExp).join("|")})`, "gi");
+  const highlight = (text: string) => text.replace(pattern, "<mark>$1</mark>");
+
+  let result = "";
+  let lastIndex = 0;
+
+  for (const match of markdown.matchAll(PROTECTED_PATTERN)) {
+    result += highlight(markdown.slice(lastIndex, match.index));
+    result += match[0];
+    lastIndex = match.index! + match[0].length;
+  }
+
+  return result + highlight(markdown.slice(lastIndex));
+}
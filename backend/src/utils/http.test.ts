@@ -0,0 +1,27 @@
+import { describe, it, expect } from "bun:test";
+import { createHostLimiter } from "@/utils/http";
+
+describe("createHostLimiter", () => {
+  it("spaces out requests to the same host", async () => {
+    const waitForHost = createHostLimiter(50);
+    const started = Date.now();
+
+    await waitForHost("https://example.com/a.xml");
+    await waitForHost("https://EXAMPLE.com/b.xml");
+    await waitForHost("https://example.com/c.xml");
+
+    expect(Date.now() - started).toBeGreaterThanOrEqual(95);
+  });
+
+  it("does not hold back other hosts", async () => {
+    const waitForHost = createHostLimiter(200);
+    const started = Date.now();
+
+    await waitForHost("https://a.example.com/feed");
+    await waitForHost("https://b.example.com/feed");
+    await waitForHost("file:///tmp/feed.xml");
+    await waitForHost("file:///tmp/other.xml");
+
+    expect(Date.now() - started).toBeLessThan(100);
+  });
+});
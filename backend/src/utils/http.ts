@@ -0,0 +1,59 @@
+/**
+ * Parse a Retry-After header (delay in seconds or an HTTP-date)
+ * @returns Delay in milliseconds, or null when the header is missing/invalid
+ */
+export function parseRetryAfter(value: string | null): number | null {
+  if (!value) return null;
+
+  const seconds = Number(value.trim());
+  if (!Number.isNaN(seconds)) {
+    return Math.max(0, seconds * 1000);
+  }
+
+  const date = Date.parse(value);
+  if (!Number.isNaN(date)) {
+    return Math.max(0, date - Date.now());
+  }
+
+  return null;
+}
+
+export function sleep(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+/**
+ * Spaces out requests to the same host by delayMs; requests to different
+ * hosts are never held back
+ * @returns A function to await before each request to url
+ */
+export function createHostLimiter(
+  delayMs: number,
+): (url: string) => Promise<void> {
+  // Earliest start time of the next request, per host
+  const nextStart = new Map<string, number>();
+
+  return async (url) => {
+    if (delayMs <= 0) {
+      return;
+    }
+
+    let host: string;
+    try {
+      host = new URL(url).host.toLowerCase();
+    } catch {
+      return;
+    }
+    // Local files have no host to protect
+    if (!host) {
+      return;
+    }
+
+    const now = Date.now();
+    const start = Math.max(now, nextStart.get(host) ?? 0);
+    nextStart.set(host, start + delayMs);
+    if (start > now) {
+      await sleep(start - now);
+    }
+  };
+}
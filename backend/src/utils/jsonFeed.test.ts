@@ -0,0 +1,66 @@
+import { describe, it, expect } from "bun:test";
+import { isJsonFeed, parseJsonFeed } from "@/utils/jsonFeed";
+
+const feed = {
+  version: "https://jsonfeed.org/version/1.1",
+  title: "My Blog",
+  home_page_url: "https://example.com/",
+  authors: [{ name: "Jane" }],
+  items: [
+    {
+      id: 1,
+      url: "https://example.com/first",
+      title: "First post",
+      content_html: "<p>Hello</p>",
+      date_published: "2024-05-01T10:00:00+02:00",
+    },
+    {
+      id: "2",
+      url: "https://example.com/episode",
+      content_text: "Line one\n\nA < B",
+      attachments: [
+        { url: "https://example.com/ep.mp3", mime_type: "audio/mpeg" },
+      ],
+    },
+  ],
+};
+
+describe("isJsonFeed", () => {
+  it("detects JSON by content type or leading brace", () => {
+    expect(isJsonFeed("", "application/feed+json")).toBe(true);
+    expect(isJsonFeed('  {"version": "x"}')).toBe(true);
+    expect(isJsonFeed("<?xml version='1.0'?><rss/>", "text/xml")).toBe(false);
+  });
+});
+
+describe("parseJsonFeed", () => {
+  it("maps feed and item fields to the rss-parser shape", () => {
+    const result = parseJsonFeed(JSON.stringify(feed));
+    const [first] = result.items;
+
+    expect(result.title).toBe("My Blog");
+    expect(result.link).toBe("https://example.com/");
+    expect(first.guid).toBe("1");
+    expect(first.link).toBe("https://example.com/first");
+    expect(first.content).toBe("<p>Hello</p>");
+    expect(first.creator).toBe("Jane");
+    expect(first.pubDate).toBe("2024-05-01T10:00:00+02:00");
+    expect(first.isoDate).toBe("2024-05-01T08:00:00.000Z");
+  });
+
+  it("escapes plain-text content and maps attachments", () => {
+    const [, second] = parseJsonFeed(JSON.stringify(feed)).items;
+
+    expect(second.content).toBe("<p>Line one</p><p>A &lt; B</p>");
+    expect(second.enclosure).toEqual({
+      url: "https://example.com/ep.mp3",
+      type: "audio/mpeg",
+      length: undefined,
+    });
+  });
+
+  it("rejects JSON that is not a JSON Feed", () => {
+    expect(() => parseJsonFeed('{"title": "x"}')).toThrow();
+    expect(() => parseJsonFeed("{not json")).toThrow();
+  });
+});
@@ -0,0 +1,96 @@
+import type Parser from "rss-parser";
+
+const JSON_FEED_VERSION = /^https?:\/\/jsonfeed\.org\/version\/1(\.\d+)?$/;
+
+/**
+ * Whether a response looks like a JSON Feed rather than XML, from its
+ * Content-Type or its first character
+ */
+export function isJsonFeed(
+  body: string,
+  contentType?: string | null,
+): boolean {
+  return (
+    /\bjson\b/i.test(contentType || "") || body.trimStart().startsWith("{")
+  );
+}
+
+/**
+ * Parse a JSON Feed (jsonfeed.org, versions 1 and 1.1) into the shape
+ * rss-parser produces, so it goes through the same conversion as RSS/Atom
+ */
+export function parseJsonFeed(body: string): Parser.Output<any> {
+  let feed: any;
+  try {
+    feed = JSON.parse(body);
+  } catch {
+    throw new Error("Invalid JSON Feed: not valid JSON");
+  }
+
+  if (
+    typeof feed !== "object" ||
+    feed === null ||
+    !JSON_FEED_VERSION.test(feed.version) ||
+    !Array.isArray(feed.items)
+  ) {
+    throw new Error("Invalid JSON Feed: missing version or items");
+  }
+
+  return {
+    title: feed.title,
+    description: feed.description,
+    link: feed.home_page_url,
+    feedUrl: feed.feed_url,
+    language: feed.language,
+    image: feed.icon ? { url: feed.icon } : undefined,
+    icon: feed.favicon,
+    items: feed.items
+      .filter((item: any) => item && typeof item === "object")
+      .map((item: any) => convertItem(item, feed)),
+  } as Parser.Output<any>;
+}
+
+function convertItem(item: any, feed: any): Record<string, any> {
+  const attachment = Array.isArray(item.attachments)
+    ? item.attachments.find((a: any) => a?.url)
+    : undefined;
+  const date = item.date_published ? new Date(item.date_published) : null;
+  const image = item.image || item.banner_image;
+  // 1.1 has an authors array; 1.0 a single author, possibly feed-wide
+  const author =
+    (item.authors || feed.authors)?.[0] || item.author || feed.author;
+
+  return {
+    guid: item.id != null ? String(item.id) : undefined,
+    link: item.url || item.external_url,
+    title: item.title,
+    content: item.content_html || textToHtml(item.content_text),
+    summary: item.summary,
+    creator: author?.name,
+    pubDate: item.date_published || item.date_modified,
+    isoDate: date && !isNaN(date.getTime()) ? date.toISOString() : undefined,
+    enclosure: attachment
+      ? {
+          url: attachment.url,
+          type: attachment.mime_type,
+          length: attachment.size_in_bytes,
+        }
+      : undefined,
+    mediaThumbnail: image ? { $: { url: image } } : undefined,
+  };
+}
+
+// Plain-text item bodies, escaped and split into paragraphs
+function textToHtml(text: unknown): string | undefined {
+  if (typeof text !== "string" || !text.trim()) {
+    return undefined;
+  }
+
+  return text
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .split(/\n{2,}/)
+    .map((paragraph) => `<p>${paragraph.trim()}</p>`)
+    .join("");
+}
@@ -0,0 +1,31 @@
+import { describe, it, expect } from "bun:test";
+import { detectLanguage } from "@/utils/language";
+
+describe("detectLanguage", () => {
+  it("detects languages by script", () => {
+    const chinese = "这是一篇关于开源软件和阅读器的中文文章内容介绍";
+    const japanese = "これはオープンソースのリーダーについての記事です";
+    const russian = "Это статья о программном обеспечении с открытым";
+
+    expect(detectLanguage(chinese)).toBe("zh");
+    expect(detectLanguage(japanese)).toBe("ja");
+    expect(detectLanguage(russian)).toBe("ru");
+  });
+
+  it("detects Latin-script languages by common words", () => {
+    expect(
+      detectLanguage("This is the story of a reader that is built with care"),
+    ).toBe("en");
+    expect(
+      detectLanguage("Le lecteur est une application pour les flux et la vie"),
+    ).toBe("fr");
+    expect(
+      detectLanguage("Der Leser ist nicht das Problem und die Zeit ist knapp"),
+    ).toBe("de");
+  });
+
+  it("returns null for short or ambiguous text", () => {
+    expect(detectLanguage("Hello")).toBeNull();
+    expect(detectLanguage("1234567890 https://example.com/feed")).toBeNull();
+  });
+});
@@ -0,0 +1,61 @@
+// Scripts that identify a language (or close family) on their own
+const SCRIPT_LANGUAGES: [RegExp, string][] = [
+  [/[\u3040-\u30ff]/g, "ja"], // Hiragana / Katakana
+  [/[\uac00-\ud7af]/g, "ko"], // Hangul
+  [/[\u4e00-\u9fff]/g, "zh"], // CJK ideographs without kana
+  [/[\u0400-\u04ff]/g, "ru"], // Cyrillic
+  [/[\u0600-\u06ff]/g, "ar"],
+  [/[\u0590-\u05ff]/g, "he"],
+  [/[\u0e00-\u0e7f]/g, "th"],
+  [/[\u0900-\u097f]/g, "hi"],
+  [/[\u0370-\u03ff]/g, "el"],
+];
+
+// Frequent short words for Latin-script languages
+const STOPWORDS: Record<string, string[]> = {
+  en: ["the", "and", "of", "to", "is", "in", "that", "for", "with", "this"],
+  fr: ["le", "la", "les", "et", "des", "est", "une", "pour", "dans", "que"],
+  de: ["der", "die", "und", "das", "ist", "nicht", "mit", "ein", "den", "zu"],
+  es: ["el", "la", "los", "y", "que", "es", "por", "una", "para", "del"],
+  it: ["il", "di", "che", "e", "la", "per", "una", "non", "sono", "del"],
+  pt: ["o", "que", "de", "e", "do", "da", "em", "um", "para", "com"],
+  nl: ["de", "het", "een", "en", "van", "is", "dat", "niet", "op", "te"],
+};
+
+const MIN_LETTERS = 20; // Below this there is too little text to judge
+
+/**
+ * Guess the language of a text, as an ISO 639-1 code
+ * Non-Latin scripts are recognized by character ranges, Latin-script
+ * languages by stopword frequency.
+ * @returns null when the text is too short or ambiguous
+ */
+export function detectLanguage(text: string): string | null {
+  const letters = text.match(/\p{L}/gu)?.length ?? 0;
+  if (letters < MIN_LETTERS) {
+    return null;
+  }
+
+  for (const [pattern, lang] of SCRIPT_LANGUAGES) {
+    // Japanese mixes in kanji, so a little kana is already decisive
+    const share = (text.match(pattern)?.length ?? 0) / letters;
+    if (share > (lang === "ja" ? 0.05 : 0.3)) {
+      return lang;
+    }
+  }
+
+  const words = text.toLowerCase().match(/\p{L}+/gu) ?? [];
+  let best: string | null = null;
+  let bestScore = 0;
+  for (const [lang, stopwords] of Object.entries(STOPWORDS)) {
+    const set = new Set(stopwords);
+    const score = words.filter((word) => set.has(word)).length;
+    if (score > bestScore) {
+      best = lang;
+      bestScore = score;
+    }
+  }
+
+  // Require a few hits so a stray "de" or "a" doesn't decide
+  return bestScore >= 3 ? best : null;
+}
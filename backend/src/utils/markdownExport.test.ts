@@ -0,0 +1,54 @@
+import { describe, it, expect } from "bun:test";
+import { articlesToMarkdown } from "@/utils/markdownExport";
+
+describe("articlesToMarkdown", () => {
+  it("groups articles by feed with link, meta line and summary", () => {
+    const markdown = articlesToMarkdown("Starred Articles", [
+      {
+        title: "First [draft]",
+        link: "https://a.example/1",
+        author: "Ann",
+        pubDate: Date.UTC(2024, 0, 15) / 1000,
+        summary: "Summary one",
+        feedTitle: "Blog A",
+      },
+      {
+        title: "Second",
+        link: "https://b.example/2",
+        feedTitle: "Blog B",
+      },
+      {
+        title: "Third",
+        link: "https://a.example/3",
+        feedTitle: "Blog A",
+      },
+    ]);
+
+    expect(markdown).toBe(
+      [
+        "# Starred Articles",
+        "",
+        "## Blog A",
+        "",
+        "### [First \\[draft\\]](<https://a.example/1>)",
+        "",
+        "_Ann · 2024-01-15_",
+        "",
+        "Summary one",
+        "",
+        "### [Third](<https://a.example/3>)",
+        "",
+        "## Blog B",
+        "",
+        "### [Second](<https://b.example/2>)",
+        "",
+      ].join("\n"),
+    );
+  });
+
+  it("renders just the heading when there is nothing to export", () => {
+    expect(articlesToMarkdown("Starred Articles", [])).toBe(
+      "# Starred Articles\n",
+    );
+  });
+});
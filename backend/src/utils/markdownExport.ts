@@ -0,0 +1,56 @@
+export interface ExportedArticle {
+  title: string;
+  link: string;
+  author?: string | null;
+  pubDate?: number | null;
+  summary?: string | null;
+  feedTitle: string;
+}
+
+/**
+ * Render articles as a Markdown document with one section per feed, in
+ * the order the articles are given
+ */
+export function articlesToMarkdown(
+  heading: string,
+  articles: ExportedArticle[],
+): string {
+  const byFeed = new Map<string, ExportedArticle[]>();
+  for (const article of articles) {
+    byFeed.set(article.feedTitle, [
+      ...(byFeed.get(article.feedTitle) || []),
+      article,
+    ]);
+  }
+
+  const lines = [`# ${heading}`, ""];
+  for (const [feedTitle, items] of byFeed) {
+    lines.push(`## ${feedTitle}`, "");
+
+    for (const article of items) {
+      lines.push(`### [${escapeLinkText(article.title)}](<${article.link}>)`);
+
+      const meta = [
+        article.author,
+        article.pubDate
+          ? new Date(article.pubDate * 1000).toISOString().slice(0, 10)
+          : null,
+      ].filter(Boolean);
+      if (meta.length > 0) {
+        lines.push("", `_${meta.join(" · ")}_`);
+      }
+
+      if (article.summary?.trim()) {
+        lines.push("", article.summary.trim());
+      }
+      lines.push("");
+    }
+  }
+
+  return lines.join("\n").trimEnd() + "\n";
+}
+
+// Brackets in a title would end the link text early
+function escapeLinkText(text: string): string {
+  return text.replace(/[[\]\\]/g, "\\$&");
+}
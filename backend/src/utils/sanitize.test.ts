@@ -0,0 +1,42 @@
+import { describe, it, expect } from "bun:test";
+import { sanitizeHtml } from "@/utils/sanitize";
+
+describe("sanitizeHtml", () => {
+  it("removes scripts and their content", () => {
+    expect(sanitizeHtml("<p>Hi</p><script>alert(1)</script>")).toBe(
+      "<p>Hi</p>",
+    );
+  });
+
+  it("removes event handler attributes", () => {
+    expect(sanitizeHtml('<img src="a.png" onerror="alert(1)">')).toBe(
+      '<img src="a.png">',
+    );
+  });
+
+  it("removes javascript: URLs, even when obfuscated", () => {
+    expect(sanitizeHtml('<a href="javascript:alert(1)">x</a>')).toBe(
+      "<a>x</a>",
+    );
+    expect(sanitizeHtml('<a href=" java\tscript:alert(1)">x</a>')).toBe(
+      "<a>x</a>",
+    );
+  });
+
+  it("keeps formatting, links and images", () => {
+    const html =
+      '<p><strong>Bold</strong> <a href="https://example.com">link</a></p>' +
+      '<img src="https://example.com/a.png" alt="A">';
+
+    expect(sanitizeHtml(html)).toBe(html);
+  });
+
+  it("keeps inline images but drops other data: URLs", () => {
+    expect(sanitizeHtml('<img src="data:image/png;base64,AAAA">')).toBe(
+      '<img src="data:image/png;base64,AAAA">',
+    );
+    expect(sanitizeHtml('<a href="data:text/html,<script>">x</a>')).toBe(
+      "<a>x</a>",
+    );
+  });
+});
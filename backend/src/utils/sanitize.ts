@@ -0,0 +1,77 @@
+import { JSDOM } from "jsdom";
+
+// Elements removed together with everything inside them
+const DANGEROUS_ELEMENTS = [
+  "script",
+  "style",
+  "noscript",
+  "template",
+  "object",
+  "embed",
+  "applet",
+  "frame",
+  "frameset",
+  "base",
+  "meta",
+  "link",
+  "form",
+  "input",
+  "button",
+  "select",
+  "textarea",
+].join(", ");
+
+// Attributes holding a URL that the browser may load or navigate to
+const URL_ATTRIBUTES = [
+  "href",
+  "src",
+  "action",
+  "formaction",
+  "poster",
+  "background",
+  "xlink:href",
+];
+
+// Schemes that run code instead of loading a resource
+const UNSAFE_URL = /^(javascript|vbscript|data):/i;
+// Inline images are the one harmless use of data: URLs
+const SAFE_DATA_URL = /^data:image\/(png|gif|jpe?g|webp);/i;
+
+/**
+ * Strip scripts, event handlers and script URLs from feed HTML while
+ * keeping formatting, images and links
+ */
+export function sanitizeHtml(html: string): string {
+  if (!html || html.trim() === "") {
+    return "";
+  }
+
+  const document = new JSDOM(html).window.document;
+  document.querySelectorAll(DANGEROUS_ELEMENTS).forEach((el) => el.remove());
+
+  for (const element of Array.from(document.body.querySelectorAll("*"))) {
+    for (const { name, value } of Array.from(element.attributes)) {
+      const attribute = name.toLowerCase();
+      // Browsers ignore whitespace and control characters in the scheme
+      const url = value.replace(/[\s\u0000-\u001f]/g, "");
+
+      if (
+        attribute.startsWith("on") ||
+        attribute === "srcdoc" ||
+        (URL_ATTRIBUTES.includes(attribute) &&
+          UNSAFE_URL.test(url) &&
+          !(attribute === "src" && SAFE_DATA_URL.test(url)))
+      ) {
+        element.removeAttribute(name);
+      }
+    }
+
+    // Each srcset candidate is a URL too
+    const srcset = element.getAttribute("srcset");
+    if (srcset && /(javascript|vbscript|data):/i.test(srcset)) {
+      element.removeAttribute("srcset");
+    }
+  }
+
+  return document.body.innerHTML;
+}
@@ -0,0 +1,22 @@
+/**
+ * Maximum bound parameters per statement, kept well under SQLite's limit
+ */
+export const MAX_SQL_PARAMS = 500;
+
+/**
+ * Split a list into chunks of at most `size` items
+ */
+export function chunk<T>(items: T[], size: number = MAX_SQL_PARAMS): T[][] {
+  const chunks: T[][] = [];
+  for (let i = 0; i < items.length; i += size) {
+    chunks.push(items.slice(i, i + size));
+  }
+  return chunks;
+}
+
+/**
+ * Build a "?, ?, ?" placeholder list for an IN (...) clause
+ */
+export function placeholders(count: number): string {
+  return new Array(count).fill("?").join(", ");
+}
@@ -0,0 +1,112 @@
+import { describe, it, expect } from "bun:test";
+import {
+  collapseWhitespace,
+  countWords,
+  decodeEntities,
+  htmlToText,
+  truncateText,
+  truncateWords,
+} from "@/utils/text";
+
+describe("truncateText", () => {
+  it("returns short text unchanged", () => {
+    expect(truncateText("Hello world", 200)).toBe("Hello world");
+  });
+
+  it("cuts at the last word boundary", () => {
+    expect(truncateText("The quick brown fox", 12)).toBe("The quick...");
+  });
+
+  it("cuts CJK text without spaces by character", () => {
+    const text = "这是一个很长的中文句子".repeat(30);
+    const result = truncateText(text, 200);
+
+    expect(Array.from(result)).toHaveLength(203);
+    expect(result.startsWith(text.slice(0, 200))).toBe(true);
+  });
+
+  it("never splits an emoji across the cut point", () => {
+    const text = "a" + "😀".repeat(250);
+    const result = truncateText(text, 200);
+
+    expect(result.endsWith("😀...")).toBe(true);
+    expect(result).not.toMatch(/[\uD800-\uDBFF](?![\uDC00-\uDFFF])/);
+  });
+});
+
+describe("htmlToText", () => {
+  it("drops tags, scripts and styles", () => {
+    const html =
+      '<p class="lead">Hello <b>world</b></p>' +
+      "<script>alert(1)</script><style>p { color: red }</style>";
+
+    expect(htmlToText(html)).toBe("Hello world");
+  });
+
+  it("decodes entities and collapses whitespace", () => {
+    expect(htmlToText("Tom &amp; Jerry&nbsp;\n\n  &lt;3")).toBe(
+      "Tom & Jerry <3",
+    );
+  });
+
+  it("separates words in adjacent blocks", () => {
+    expect(htmlToText("<p>One</p><p>Two</p>")).toBe("One Two");
+  });
+});
+
+describe("decodeEntities", () => {
+  it("decodes named and numeric entities", () => {
+    expect(decodeEntities("Tom &amp; Jerry&#8217;s &hellip; &#x2014;")).toBe(
+      "Tom & Jerry\u2019s \u2026 \u2014",
+    );
+  });
+
+  it("leaves markup as literal text", () => {
+    expect(decodeEntities("&lt;b&gt; is <i>bold</i>")).toBe(
+      "<b> is <i>bold</i>",
+    );
+  });
+});
+
+describe("truncateWords", () => {
+  it("returns text within the limit unchanged", () => {
+    expect(truncateWords("one two three", 3)).toBe("one two three");
+  });
+
+  it("keeps the first words", () => {
+    expect(truncateWords("one  two\nthree four", 2)).toBe("one two...");
+  });
+});
+
+describe("countWords", () => {
+  it("counts space separated words", () => {
+    expect(countWords("The quick brown fox jumps")).toBe(5);
+  });
+
+  it("ignores punctuation-only tokens", () => {
+    expect(countWords("Hello — world !")).toBe(2);
+  });
+
+  it("counts each CJK character as a word", () => {
+    expect(countWords("这是中文")).toBe(4);
+    expect(countWords("Rust 是一门语言")).toBe(6);
+  });
+
+  it("returns 0 for empty text", () => {
+    expect(countWords("")).toBe(0);
+    expect(countWords("   ")).toBe(0);
+  });
+});
+
+describe("collapseWhitespace", () => {
+  it("trims and collapses tabs and newlines", () => {
+    expect(collapseWhitespace("\n\t Breaking:\n  new\trelease  ")).toBe(
+      "Breaking: new release",
+    );
+  });
+
+  it("leaves clean text unchanged", () => {
+    expect(collapseWhitespace("Jane Doe")).toBe("Jane Doe");
+    expect(collapseWhitespace("   ")).toBe("");
+  });
+});
@@ -0,0 +1,98 @@
+import { JSDOM } from "jsdom";
+
+// Elements whose text is never visible on the page
+const INVISIBLE_ELEMENTS = "script, style, noscript, template, head";
+// Elements that separate words even without surrounding whitespace
+const BREAKING_ELEMENTS = "br, p, div, li, h1, h2, h3, h4, h5, h6";
+
+/**
+ * Extract the visible text of an HTML fragment
+ * Entities are decoded by the parser and whitespace is collapsed
+ */
+export function htmlToText(html: string): string {
+  if (!html || html.trim() === "") {
+    return "";
+  }
+
+  const document = new JSDOM(html).window.document;
+  document.querySelectorAll(INVISIBLE_ELEMENTS).forEach((el) => el.remove());
+
+  document.querySelectorAll(BREAKING_ELEMENTS).forEach((el) => el.append(" "));
+
+  return (document.body.textContent || "").replace(/\s+/g, " ").trim();
+}
+
+// Shared document for entity decoding; creating a JSDOM per call is slow
+let decoderDocument: JSDOM["window"]["document"] | null = null;
+
+/**
+ * Decode HTML entities (&amp;, &#8217;, &hellip;...) in plain text such as
+ * titles. Markup is not interpreted: "<b>" stays as literal text.
+ */
+export function decodeEntities(text: string): string {
+  if (!text || !text.includes("&")) {
+    return text;
+  }
+
+  decoderDocument ??= new JSDOM("").window.document;
+  // A textarea's content is raw text, so only entities get parsed
+  const textarea = decoderDocument.createElement("textarea");
+  textarea.innerHTML = text;
+  return textarea.value;
+}
+
+// Scripts written without spaces between words
+const UNSPACED_CHAR = /[\p{Script=Han}\p{Script=Hiragana}\p{Script=Katakana}]/gu;
+
+/**
+ * Count the words in plain text. Chinese and Japanese characters count as
+ * one word each since those scripts do not separate words with spaces.
+ */
+export function countWords(text: string): number {
+  const unspaced = text.match(UNSPACED_CHAR)?.length ?? 0;
+  const spaced = text
+    .replace(UNSPACED_CHAR, " ")
+    .split(/\s+/)
+    .filter((word) => /[\p{L}\p{N}]/u.test(word)).length;
+
+  return unspaced + spaced;
+}
+
+/**
+ * Shorten text to at most maxLength characters, cutting at the last word
+ * boundary and appending an ellipsis when anything was removed
+ * Lengths count code points so emoji and other astral characters are never
+ * split into lone surrogates
+ */
+export function truncateText(text: string, maxLength: number): string {
+  const chars = Array.from(text);
+  if (chars.length <= maxLength) {
+    return text;
+  }
+
+  const cut = chars.slice(0, maxLength).join("");
+  const lastSpace = cut.lastIndexOf(" ");
+
+  // Text without spaces (e.g. CJK) has no word boundary to respect
+  return (lastSpace > 0 ? cut.slice(0, lastSpace) : cut).trimEnd() + "...";
+}
+
+/**
+ * Keep at most maxWords whitespace-separated words, appending an ellipsis
+ * when anything was removed
+ */
+export function truncateWords(text: string, maxWords: number): string {
+  const words = text.trim().split(/\s+/);
+  if (words.length <= maxWords) {
+    return text;
+  }
+
+  return words.slice(0, maxWords).join(" ") + "...";
+}
+
+/**
+ * Trim and collapse runs of whitespace (tabs, newlines...) to single spaces
+ */
+export function collapseWhitespace(text: string): string {
+  return text.replace(/\s+/g, " ").trim();
+}
@@ -0,0 +1,43 @@
+import { describe, it, expect } from "bun:test";
+import { findDuplicateUrl, normalizeFeedUrl } from "@/utils/url";
+
+describe("normalizeFeedUrl", () => {
+  it("trims, lowercases the host and strips trailing slashes", () => {
+    expect(normalizeFeedUrl("  https://Example.COM/Feed/  ")).toBe(
+      "https://example.com/Feed",
+    );
+    expect(normalizeFeedUrl("https://example.com/")).toBe(
+      "https://example.com",
+    );
+  });
+
+  it("drops default ports and fragments", () => {
+    expect(normalizeFeedUrl("HTTPS://example.com:443/feed#top")).toBe(
+      "https://example.com/feed",
+    );
+    expect(normalizeFeedUrl("http://example.com:8080/feed//")).toBe(
+      "http://example.com:8080/feed",
+    );
+  });
+
+  it("leaves query strings alone", () => {
+    expect(normalizeFeedUrl("https://example.com/?feed=rss2")).toBe(
+      "https://example.com/?feed=rss2",
+    );
+  });
+
+  it("returns unparsable input trimmed", () => {
+    expect(normalizeFeedUrl(" not a url ")).toBe("not a url");
+  });
+});
+
+describe("findDuplicateUrl", () => {
+  it("matches trivial variants of a subscribed URL", () => {
+    const feeds = [{ id: "1", url: "https://example.com/feed" }];
+
+    expect(findDuplicateUrl("https://EXAMPLE.com/feed/", feeds)?.id).toBe("1");
+    expect(
+      findDuplicateUrl("https://example.com/other", feeds),
+    ).toBeUndefined();
+  });
+});
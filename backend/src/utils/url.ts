@@ -0,0 +1,34 @@
+/**
+ * Canonical form of a feed URL, stored for new subscriptions so the UNIQUE
+ * constraint catches duplicates: trimmed, lowercase scheme and host, no
+ * default port, fragment or trailing slashes
+ */
+export function normalizeFeedUrl(url: string): string {
+  const trimmed = url.trim();
+
+  let parsed: URL;
+  try {
+    parsed = new URL(trimmed);
+  } catch {
+    return trimmed;
+  }
+
+  // URL already lowercases the scheme and host and drops default ports.
+  // A root path always serializes as "/", so that slash is removed from the
+  // final string instead
+  parsed.hash = "";
+  parsed.pathname = parsed.pathname.replace(/\/+$/, "");
+  const href = parsed.href;
+  return parsed.search ? href : href.replace(/\/$/, "");
+}
+
+/**
+ * Find the subscription whose URL normalizes to the same value
+ */
+export function findDuplicateUrl<T extends { url: string }>(
+  url: string,
+  candidates: T[],
+): T | undefined {
+  const normalized = normalizeFeedUrl(url);
+  return candidates.find((c) => normalizeFeedUrl(c.url) === normalized);
+}
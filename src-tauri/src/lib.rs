@@ -4,7 +4,8 @@ use std::process::{Child, Command, Stdio};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
 
 #[derive(Default, Clone)]
 struct AppState {
@@ -117,7 +118,10 @@ fn start_backend(
 
     let (tx, rx) = std::sync::mpsc::channel::<Result<u16, String>>();
     let port_mutex = Arc::clone(&state.backend_port);
+    let app = app.clone();
 
+    // Keep reading after the port is known: the backend reports events
+    // (e.g. finished background refreshes) on the same stream
     thread::spawn(move || {
         for line in reader.lines() {
             if let Ok(line) = line {
@@ -127,8 +131,9 @@ fn start_backend(
                         *port_mutex.lock().unwrap() = Some(port);
                         eprintln!("[Sidecar] Backend started on port: {}", port);
                         let _ = tx.send(Ok(port));
-                        break;
                     }
+                } else if line.starts_with("EVENT:") {
+                    forward_event(&app, &line);
                 }
             }
         }
@@ -149,6 +154,40 @@ fn start_backend(
     }
 }
 
+// Re-emit an `EVENT:<name>:<json>` line from the backend as a Tauri event
+fn forward_event(app: &tauri::AppHandle, line: &str) {
+    let Some((name, payload)) = line.trim_start_matches("EVENT:").split_once(':') else {
+        return;
+    };
+
+    let payload = match serde_json::from_str::<serde_json::Value>(payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            eprintln!("[Sidecar] Invalid payload for event '{}': {}", name, e);
+            return;
+        }
+    };
+
+    // Desktop notifications are shown by the shell rather than the webview
+    if name == "notification" {
+        show_notification(app, &payload);
+        return;
+    }
+
+    if let Err(e) = app.emit(name, payload) {
+        eprintln!("[Sidecar] Failed to emit event '{}': {}", name, e);
+    }
+}
+
+fn show_notification(app: &tauri::AppHandle, payload: &serde_json::Value) {
+    let title = payload["title"].as_str().unwrap_or("RSS Reader");
+    let body = payload["body"].as_str().unwrap_or_default();
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("[Sidecar] Failed to show notification: {}", e);
+    }
+}
+
 fn stop_backend(state: &AppState) {
     eprintln!("[Sidecar] Stopping backend...");
     if let Some(mut child) = state.backend_process.lock().unwrap().take() {
@@ -220,6 +259,7 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(state.clone())
         .setup(move |app| {
             let state = app.state::<AppState>();
@@ -240,6 +280,12 @@ pub fn run() {
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![get_backend_port])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Take the backend (and its refresh scheduler) down with the app
+            if let tauri::RunEvent::Exit = event {
+                stop_backend(&app.state::<AppState>());
+            }
+        });
 }
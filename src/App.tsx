@@ -9,6 +9,7 @@ import { useAppStore, Article } from "@/stores/useAppStore";
 import { useKeyboardShortcuts } from "@/hooks/useKeyboardShortcuts";
 import { useQuery, useQueryClient } from "@tanstack/react-query";
 import { api } from "@/lib/api";
+import { listenBackendEvent, type NewArticlesEvent } from "@/lib/events";
 
 interface RefreshProgress {
   current: number;
@@ -69,6 +70,23 @@ function App() {
     loadSettings();
   }, [updateSettings]);
 
+  // Pick up articles saved by the backend, including its auto-refresh
+  useEffect(() => {
+    const unlisteners = [
+      listenBackendEvent<NewArticlesEvent>("new-articles", () => {
+        queryClient.invalidateQueries({ queryKey: ["articles"] });
+      }),
+      listenBackendEvent<{ count: number }>("feeds-refreshed", () => {
+        queryClient.invalidateQueries({ queryKey: ["articles"] });
+        queryClient.invalidateQueries({ queryKey: ["feeds"] });
+      }),
+    ];
+
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()));
+    };
+  }, [queryClient]);
+
   // Fetch articles for keyboard navigation - use same query key as ArticleList
   const limit = 50;
   const { data: articlesData } = useQuery({
@@ -172,6 +190,8 @@ function App() {
     }
 
     await queryClient.invalidateQueries({ queryKey: ["articles"] });
+    // Feeds carry the latest refresh error
+    await queryClient.invalidateQueries({ queryKey: ["feeds"] });
 
     const finalMessage =
       errorCount > 0
@@ -230,6 +250,7 @@ function App() {
     try {
       const result = await api.feeds.refresh(feedId);
       await queryClient.invalidateQueries({ queryKey: ["articles"] });
+      await queryClient.invalidateQueries({ queryKey: ["feeds"] });
 
       if (result.success) {
         setProgress({
@@ -286,7 +307,8 @@ function App() {
   // Keyboard shortcuts
   useKeyboardShortcuts({
     articles,
-    selectedArticleId: selectedArticle?.id || null,
+    selectedArticle,
+    feedId: selectedFeedId,
     onSelectArticle: setSelectedArticle,
     onRefresh: handleRefresh,
     onToggleTheme: handleToggleTheme,
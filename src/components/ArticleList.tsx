@@ -84,7 +84,7 @@ export function ArticleList({
         (old) => {
           if (!old) return old;
           return old.map((a) =>
-            a.id === article.id ? { ...a, isRead: 1 } : a,
+            a.id === article.id ? { ...a, isRead: true } : a,
           );
         },
       );
@@ -178,11 +178,11 @@ export function ArticleList({
                 className={cn(
                   "p-3 cursor-pointer transition-colors hover:bg-muted/50 relative",
                   selectedArticleId === article.id && "bg-muted",
-                  article.isRead === 0 && !selectedArticleId && "bg-background",
+                  !article.isRead && !selectedArticleId && "bg-background",
                 )}
               >
                 {/* Star indicator */}
-                {article.isStarred === 1 && (
+                {article.isStarred && (
                   <span className="absolute top-2 right-2 text-yellow-500">
                     <Icon icon="mdi:star" className="text-base" />
                   </span>
@@ -209,10 +209,23 @@ export function ArticleList({
                   )}
                   <span>{formatArticleDate(article.pubDate)}</span>
                 </div>
-                {article.summary && (
-                  <p className="text-xs text-muted-foreground mt-1 line-clamp-2">
-                    {article.summary}
-                  </p>
+                {(article.summary || article.imageUrl) && (
+                  <div className="flex items-start gap-2 mt-1">
+                    <p className="flex-1 text-xs text-muted-foreground line-clamp-2">
+                      {article.summary}
+                    </p>
+                    {article.imageUrl && (
+                      <img
+                        src={article.imageUrl}
+                        alt=""
+                        loading="lazy"
+                        onError={(e) =>
+                          (e.currentTarget.style.display = "none")
+                        }
+                        className="w-12 h-12 object-cover rounded flex-shrink-0"
+                      />
+                    )}
+                  </div>
                 )}
               </article>
             ))}
@@ -1,7 +1,10 @@
 import { useState, useEffect, useRef, useCallback } from "react";
+import { useQueryClient } from "@tanstack/react-query";
 import { useAppStore, Article } from "@/stores/useAppStore";
 import { Icon } from "@iconify-icon/react";
 import { api } from "@/lib/api";
+import { openLink } from "@/lib/links";
+import { formatReadingTime } from "@/lib/utils";
 import { MarkdownRenderer } from "./MarkdownRenderer";
 
 interface ArticleViewProps {
@@ -27,6 +30,7 @@ const SEPARATOR = "\n\n===CONTENT===\n\n";
 
 export function ArticleView({ article }: ArticleViewProps) {
   const { feeds } = useAppStore();
+  const queryClient = useQueryClient();
   const [viewMode, setViewMode] = useState<ViewMode>("original");
   const [phase, setPhase] = useState<TranslationPhase>("idle");
 
@@ -36,6 +40,7 @@ export function ArticleView({ article }: ArticleViewProps) {
   const [displayedTitle, setDisplayedTitle] = useState<string>("");
   const [displayedContent, setDisplayedContent] = useState<string>("");
   const [hasTranslation, setHasTranslation] = useState(false);
+  const [isFetchingFull, setIsFetchingFull] = useState(false);
 
   // Toasts - only for errors
   const [toasts, setToasts] = useState<Toast[]>([]);
@@ -188,16 +193,27 @@ export function ArticleView({ article }: ArticleViewProps) {
       abortControllerRef.current = new AbortController();
 
       try {
-        // Start both translations
+        // The title is short; translate it while the content streams
         const titlePromise = title.trim()
-          ? api.translation.translate(title.slice(0, 200), "zh")
+          ? api.translation.translate(title.slice(0, 200))
           : Promise.resolve({ translatedText: "" });
 
+        // Show the content as it arrives instead of waiting for all of it
         const contentPromise = content.trim()
-          ? api.translation.translate(content.slice(0, 5000), "zh")
-          : Promise.resolve({ translatedText: "" });
-
-        const [titleResult, contentResult] = await Promise.all([
+          ? api.translation.translateStream(
+              content.slice(0, 5000),
+              "",
+              (partial) => {
+                if (!isActiveRef.current) return;
+                setHasTranslation(true);
+                setPhase("streaming");
+                setDisplayedContent(partial);
+              },
+              abortControllerRef.current.signal,
+            )
+          : Promise.resolve("");
+
+        const [titleResult, translatedContentText] = await Promise.all([
           titlePromise,
           contentPromise,
         ]);
@@ -207,7 +223,6 @@ export function ArticleView({ article }: ArticleViewProps) {
 
         // Translation results are already in Markdown format (no need to clean)
         const translatedTitleText = titleResult.translatedText;
-        const translatedContentText = contentResult.translatedText;
 
         setTranslatedTitle(translatedTitleText);
         setTranslatedContent(translatedContentText);
@@ -223,9 +238,6 @@ export function ArticleView({ article }: ArticleViewProps) {
         if (translatedTitleText && isActiveRef.current) {
           await streamText(translatedTitleText, setDisplayedTitle, 15);
         }
-        if (translatedContentText && isActiveRef.current) {
-          await streamText(translatedContentText, setDisplayedContent, 8);
-        }
 
         if (isActiveRef.current) {
           setPhase("completed");
@@ -287,14 +299,34 @@ export function ArticleView({ article }: ArticleViewProps) {
   const handleOpenOriginal = async () => {
     if (article) {
       try {
-        const { openUrl } = await import("@tauri-apps/plugin-opener");
-        await openUrl(article.link);
+        await openLink(article.link, article.id);
+        queryClient.invalidateQueries({ queryKey: ["articles"] });
       } catch (e) {
         addErrorToast(`Failed to open: ${e}`);
       }
     }
   };
 
+  const handleFetchFullContent = async () => {
+    if (!article || isFetchingFull) return;
+
+    setIsFetchingFull(true);
+    try {
+      const { content, wordCount } = await api.articles.fetchFullContent(
+        article.id,
+      );
+      article.content = content;
+      article.wordCount = wordCount;
+      queryClient.invalidateQueries({ queryKey: ["articles"] });
+    } catch (e) {
+      addErrorToast(
+        e instanceof Error ? e.message : "Failed to fetch full content",
+      );
+    } finally {
+      setIsFetchingFull(false);
+    }
+  };
+
   const handleTranslate = () => {
     if (!article || phase === "loading" || phase === "streaming") return;
 
@@ -336,12 +368,11 @@ export function ArticleView({ article }: ArticleViewProps) {
 
   const handleToggleStar = async () => {
     if (!article) return;
-    const currentlyStarred = article.isStarred === 1;
-    const newStarred = !currentlyStarred;
+    const newStarred = !article.isStarred;
 
     try {
       await api.articles.toggleStarred(article.id, newStarred);
-      article.isStarred = newStarred ? 1 : 0;
+      article.isStarred = newStarred;
     } catch {
       addErrorToast("Failed to toggle star");
     }
@@ -387,6 +418,8 @@ export function ArticleView({ article }: ArticleViewProps) {
     );
   }
 
+  const readingTime = formatReadingTime(article.wordCount);
+
   return (
     <div className="flex-1 flex flex-col h-full overflow-hidden relative">
       {/* Error Toast Notifications */}
@@ -428,6 +461,12 @@ export function ArticleView({ article }: ArticleViewProps) {
                 </span>
               )}
               <span>{formatDate(article.pubDate)}</span>
+              {readingTime && (
+                <span className="flex items-center gap-1">
+                  <Icon icon="mdi:clock-outline" className="text-sm" />
+                  {readingTime}
+                </span>
+              )}
             </div>
           </div>
 
@@ -437,12 +476,30 @@ export function ArticleView({ article }: ArticleViewProps) {
             title="Toggle star"
           >
             <Icon
-              icon={article.isStarred === 1 ? "mdi:star" : "mdi:star-outline"}
-              className={article.isStarred === 1 ? "text-yellow-500" : ""}
+              icon={article.isStarred ? "mdi:star" : "mdi:star-outline"}
+              className={article.isStarred ? "text-yellow-500" : ""}
             />
           </button>
         </div>
 
+        {/* Podcast / media enclosure */}
+        {article.enclosureUrl &&
+          (article.enclosureType?.startsWith("video/") ? (
+            <video
+              controls
+              preload="none"
+              src={article.enclosureUrl}
+              className="w-full mt-3 rounded-md"
+            />
+          ) : (
+            <audio
+              controls
+              preload="none"
+              src={article.enclosureUrl}
+              className="w-full mt-3"
+            />
+          ))}
+
         {/* Action buttons */}
         <div className="flex items-center gap-2 mt-3 flex-wrap">
           <button
@@ -453,6 +510,19 @@ export function ArticleView({ article }: ArticleViewProps) {
             Open Original
           </button>
 
+          <button
+            onClick={handleFetchFullContent}
+            disabled={isFetchingFull}
+            className="px-3 py-1.5 text-sm bg-secondary text-secondary-foreground rounded-md hover:bg-secondary/70 hover:shadow-md active:scale-95 transition-all flex items-center gap-1 cursor-pointer disabled:opacity-50"
+            title="Download the full article from its web page"
+          >
+            <Icon
+              icon={isFetchingFull ? "mdi:loading" : "mdi:text-box-search"}
+              className={`text-sm ${isFetchingFull ? "animate-spin" : ""}`}
+            />
+            {isFetchingFull ? "Fetching..." : "Full Article"}
+          </button>
+
           {/* View mode toggle - show when has translation or translating */}
           {(hasTranslation || isTranslating) && (
             <div className="flex rounded-md overflow-hidden border border-border">
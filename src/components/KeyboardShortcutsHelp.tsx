@@ -14,6 +14,8 @@ interface ShortcutItem {
 const shortcuts: ShortcutItem[] = [
   { keys: ["j", "↓"], action: "Next article" },
   { keys: ["k", "↑"], action: "Previous article" },
+  { keys: ["n"], action: "Next unread article" },
+  { keys: ["p"], action: "Previous unread article" },
   { keys: ["o", "Enter"], action: "Open in browser" },
   { keys: ["r"], action: "Refresh feeds" },
   { keys: ["m"], action: "Toggle theme" },
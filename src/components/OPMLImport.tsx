@@ -1,8 +1,15 @@
 import { useState, useRef } from "react";
 import { useQueryClient } from "@tanstack/react-query";
-import { exportToOPML, downloadOPML, importFromOPML } from "@/lib/opml";
+import {
+  exportToOPML,
+  downloadOPML,
+  importFromOPML,
+  type ImportProgress,
+  type ImportResult,
+} from "@/lib/opml";
 import { useAppStore } from "@/stores/useAppStore";
-import { cn } from "@/lib/utils";
+import { api } from "@/lib/api";
+import { cn, downloadFile } from "@/lib/utils";
 import { Icon } from "@iconify-icon/react";
 
 interface OPMLImportProps {
@@ -14,10 +21,11 @@ export function OPMLImport({ isOpen, onClose }: OPMLImportProps) {
   const [tab, setTab] = useState<"import" | "export">("import");
   const [fileContent, setFileContent] = useState("");
   const [importing, setImporting] = useState(false);
-  const [importResult, setImportResult] = useState<{
-    count: number;
-    errors: string[];
-  } | null>(null);
+  const [fetchAfterImport, setFetchAfterImport] = useState(true);
+  const [progress, setProgress] = useState<ImportProgress | null>(null);
+  const [importResult, setImportResult] = useState<ImportResult | null>(
+    null,
+  );
   const fileInputRef = useRef<HTMLInputElement>(null);
   const { feeds } = useAppStore();
   const queryClient = useQueryClient();
@@ -37,14 +45,27 @@ export function OPMLImport({ isOpen, onClose }: OPMLImportProps) {
 
     setImporting(true);
     try {
-      const result = await importFromOPML(fileContent);
+      const result = await importFromOPML(fileContent, {
+        fetchAfterImport,
+        onProgress: setProgress,
+      });
       setImportResult(result);
       if (result.count > 0) {
         queryClient.invalidateQueries({ queryKey: ["feeds"] });
       }
+      if (result.fetched > 0) {
+        queryClient.invalidateQueries({ queryKey: ["articles"] });
+      }
     } catch (e) {
-      setImportResult({ count: 0, errors: [`Import failed: ${e}`] });
+      setImportResult({
+        count: 0,
+        created: [],
+        skipped: 0,
+        errors: [`Import failed: ${e}`],
+        fetched: 0,
+      });
     }
+    setProgress(null);
     setImporting(false);
   };
 
@@ -53,8 +74,21 @@ export function OPMLImport({ isOpen, onClose }: OPMLImportProps) {
     downloadOPML(content, "rss-reader-subscriptions.opml");
   };
 
+  const handleExportStarred = async () => {
+    try {
+      const content = await api.articles.exportStarredMarkdown();
+      downloadFile(content, "starred-articles.md", "text/markdown");
+    } catch (e) {
+      console.error("Failed to export starred articles:", e);
+    }
+  };
+
   if (!isOpen) return null;
 
+  const importingLabel = progress
+    ? `${progress.phase === "fetching" ? "Fetching" : "Importing"} ${progress.completed}/${progress.total}...`
+    : "Importing...";
+
   return (
     <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50">
       <div className="bg-background rounded-lg shadow-xl w-full max-w-md border border-border">
@@ -137,12 +171,16 @@ export function OPMLImport({ isOpen, onClose }: OPMLImportProps) {
                 <div
                   className={cn(
                     "p-3 rounded text-sm",
-                    importResult.count > 0
+                    importResult.count > 0 || importResult.skipped > 0
                       ? "bg-green-500/10 text-green-500"
                       : "bg-destructive/10 text-destructive",
                   )}
                 >
                   Imported {importResult.count} feeds
+                  {importResult.fetched > 0 &&
+                    ` with ${importResult.fetched} articles`}
+                  {importResult.skipped > 0 &&
+                    `, skipped ${importResult.skipped} already subscribed`}
                   {importResult.errors.length > 0 && (
                     <ul className="mt-2 text-xs">
                       {importResult.errors.map((e, i) => (
@@ -153,6 +191,16 @@ export function OPMLImport({ isOpen, onClose }: OPMLImportProps) {
                 </div>
               )}
 
+              <label className="flex items-center gap-2 text-sm">
+                <input
+                  type="checkbox"
+                  checked={fetchAfterImport}
+                  onChange={(e) => setFetchAfterImport(e.target.checked)}
+                  disabled={importing}
+                />
+                Fetch articles after import
+              </label>
+
               <div className="flex justify-end gap-2">
                 <button
                   onClick={onClose}
@@ -165,7 +213,7 @@ export function OPMLImport({ isOpen, onClose }: OPMLImportProps) {
                   disabled={!fileContent.trim() || importing}
                   className="px-4 py-2 text-sm bg-primary text-primary-foreground rounded hover:bg-primary/90 disabled:opacity-50"
                 >
-                  {importing ? "Importing..." : "Import"}
+                  {importing ? importingLabel : "Import"}
                 </button>
               </div>
             </div>
@@ -197,6 +245,12 @@ export function OPMLImport({ isOpen, onClose }: OPMLImportProps) {
                 >
                   Cancel
                 </button>
+                <button
+                  onClick={handleExportStarred}
+                  className="px-4 py-2 text-sm bg-muted rounded hover:bg-muted/80"
+                >
+                  Export Starred (Markdown)
+                </button>
                 <button
                   onClick={handleExport}
                   disabled={feeds.length === 0}
@@ -1,7 +1,9 @@
 import { useState, useEffect } from "react";
-import { useAppStore } from "@/stores/useAppStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useAppStore, type TranslationProvider } from "@/stores/useAppStore";
 import { Icon } from "@iconify-icon/react";
-import { api } from "@/lib/api";
+import { api, type DatabaseOptimization } from "@/lib/api";
+import { formatBytes } from "@/lib/utils";
 
 interface SettingsProps {
   isOpen: boolean;
@@ -11,6 +13,13 @@ interface SettingsProps {
 export function Settings({ isOpen, onClose }: SettingsProps) {
   const { settings, updateSettings } = useAppStore();
   const [formData, setFormData] = useState(settings);
+  const [isOptimizing, setIsOptimizing] = useState(false);
+  const [optimization, setOptimization] =
+    useState<DatabaseOptimization | null>(null);
+  const [optimizeError, setOptimizeError] = useState<string | null>(null);
+  const [isTranslatingTitles, setIsTranslatingTitles] = useState(false);
+  const [titlesMessage, setTitlesMessage] = useState<string | null>(null);
+  const queryClient = useQueryClient();
 
   // Reset form when opened
   useEffect(() => {
@@ -25,6 +34,7 @@ export function Settings({ isOpen, onClose }: SettingsProps) {
 
     // Save to backend database for translation settings
     try {
+      await api.settings.set("translation_provider", formData.provider || "");
       await api.settings.set("translation_base_url", formData.baseUrl);
       await api.settings.set("translation_api_key", formData.apiKey);
       await api.settings.set("translation_model", formData.model);
@@ -38,16 +48,19 @@ export function Settings({ isOpen, onClose }: SettingsProps) {
 
   const handleReset = async () => {
     const defaultSettings = {
+      provider: "" as const,
       apiKey: "",
       baseUrl: "https://libretranslate.com",
       model: "gpt-3.5-turbo",
       prompt: "Translate the following text to Chinese:",
+      showTranslatedTitles: false,
     };
     setFormData(defaultSettings);
     updateSettings(defaultSettings);
 
     // Also reset backend settings
     try {
+      await api.settings.set("translation_provider", defaultSettings.provider);
       await api.settings.set("translation_base_url", defaultSettings.baseUrl);
       await api.settings.set("translation_api_key", defaultSettings.apiKey);
       await api.settings.set("translation_model", defaultSettings.model);
@@ -57,6 +70,36 @@ export function Settings({ isOpen, onClose }: SettingsProps) {
     }
   };
 
+  const handleTranslateTitles = async () => {
+    setIsTranslatingTitles(true);
+    setTitlesMessage(null);
+    try {
+      const { count } = await api.feeds.translateTitles();
+      setTitlesMessage(`Translated ${count} feed titles`);
+      queryClient.invalidateQueries({ queryKey: ["feeds"] });
+    } catch (e) {
+      setTitlesMessage(
+        e instanceof Error ? e.message : "Failed to translate feed titles",
+      );
+    } finally {
+      setIsTranslatingTitles(false);
+    }
+  };
+
+  const handleOptimize = async () => {
+    setIsOptimizing(true);
+    setOptimizeError(null);
+    try {
+      setOptimization(await api.database.optimize());
+    } catch (e) {
+      setOptimizeError(
+        e instanceof Error ? e.message : "Failed to optimize database",
+      );
+    } finally {
+      setIsOptimizing(false);
+    }
+  };
+
   if (!isOpen) return null;
 
   return (
@@ -90,6 +133,31 @@ export function Settings({ isOpen, onClose }: SettingsProps) {
             </p>
 
             <div className="space-y-4">
+              {/* Provider */}
+              <div>
+                <label className="block text-sm font-medium mb-1">
+                  Translation Provider
+                </label>
+                <select
+                  value={formData.provider || ""}
+                  onChange={(e) =>
+                    setFormData({
+                      ...formData,
+                      provider: e.target.value as TranslationProvider | "",
+                    })
+                  }
+                  className="w-full px-3 py-2 text-sm border border-input rounded-md bg-background"
+                >
+                  <option value="">Auto-detect from URL</option>
+                  <option value="openai">OpenAI-compatible</option>
+                  <option value="libretranslate">LibreTranslate</option>
+                  <option value="deepl">DeepL</option>
+                </select>
+                <p className="text-xs text-muted-foreground mt-1">
+                  DeepL ignores the base URL and only needs an API key
+                </p>
+              </div>
+
               {/* API Base URL */}
               <div>
                 <label className="block text-sm font-medium mb-1">
@@ -166,9 +234,80 @@ export function Settings({ isOpen, onClose }: SettingsProps) {
                   The prompt sent to the translation service
                 </p>
               </div>
+
+              {/* Translated Feed Titles */}
+              <div>
+                <label className="flex items-center gap-2 text-sm font-medium">
+                  <input
+                    type="checkbox"
+                    checked={formData.showTranslatedTitles ?? false}
+                    onChange={(e) =>
+                      setFormData({
+                        ...formData,
+                        showTranslatedTitles: e.target.checked,
+                      })
+                    }
+                  />
+                  Show translated feed titles
+                </label>
+                <p className="text-xs text-muted-foreground mt-1">
+                  Feeds without a translation keep their original title
+                </p>
+                <button
+                  onClick={handleTranslateTitles}
+                  disabled={isTranslatingTitles}
+                  className="mt-2 px-3 py-1.5 text-sm bg-muted rounded hover:bg-muted/80 transition-colors flex items-center gap-1 disabled:opacity-50"
+                >
+                  <Icon
+                    icon={isTranslatingTitles ? "mdi:loading" : "mdi:translate"}
+                    className={isTranslatingTitles ? "animate-spin" : ""}
+                  />
+                  {isTranslatingTitles
+                    ? "Translating..."
+                    : "Translate Feed Titles"}
+                </button>
+                {titlesMessage && (
+                  <p className="text-xs text-muted-foreground mt-2">
+                    {titlesMessage}
+                  </p>
+                )}
+              </div>
             </div>
           </div>
 
+          {/* Maintenance Section */}
+          <div>
+            <h3 className="text-sm font-medium mb-3 flex items-center gap-2">
+              <Icon icon="mdi:database-cog" className="text-primary" />
+              Maintenance
+            </h3>
+            <p className="text-xs text-muted-foreground mb-3">
+              Check the database for damage and reclaim space left by deleted
+              articles.
+            </p>
+            <button
+              onClick={handleOptimize}
+              disabled={isOptimizing}
+              className="px-3 py-1.5 text-sm bg-muted rounded hover:bg-muted/80 transition-colors flex items-center gap-1 disabled:opacity-50"
+            >
+              <Icon
+                icon={isOptimizing ? "mdi:loading" : "mdi:broom"}
+                className={isOptimizing ? "animate-spin" : ""}
+              />
+              {isOptimizing ? "Optimizing..." : "Optimize Database"}
+            </button>
+            {optimization && (
+              <p className="text-xs text-muted-foreground mt-2">
+                {optimization.integrity === "ok"
+                  ? `Integrity OK. ${formatBytes(optimization.sizeBefore)} → ${formatBytes(optimization.sizeAfter)}`
+                  : `Integrity check failed: ${optimization.integrity}`}
+              </p>
+            )}
+            {optimizeError && (
+              <p className="text-xs text-destructive mt-2">{optimizeError}</p>
+            )}
+          </div>
+
           {/* Reset Button */}
           <div className="pt-4 border-t border-border">
             <button
@@ -3,17 +3,7 @@ import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { useAppStore } from "@/stores/useAppStore";
 import { cn } from "@/lib/utils";
 import { Icon } from "@iconify-icon/react";
-import { api } from "@/lib/api";
-
-// Extract domain from URL to use as default feed name
-function extractDomainFromUrl(url: string): string {
-  try {
-    const urlObj = new URL(url);
-    return urlObj.hostname.replace(/^www\./, "");
-  } catch {
-    return "New Feed";
-  }
-}
+import { api, ApiError } from "@/lib/api";
 
 interface ContextMenuState {
   show: boolean;
@@ -44,8 +34,14 @@ export function Sidebar({
   isRefreshing,
   onRefreshFeed,
 }: SidebarProps) {
-  const { feeds, setFeeds, selectedFeedId, setSelectedFeedId, theme } =
-    useAppStore();
+  const {
+    feeds,
+    setFeeds,
+    selectedFeedId,
+    setSelectedFeedId,
+    theme,
+    settings,
+  } = useAppStore();
   const [newFeedUrl, setNewFeedUrl] = useState("");
   const [isAdding, setIsAdding] = useState(false);
   const [editingFeed, setEditingFeed] = useState<{
@@ -98,21 +94,36 @@ export function Sidebar({
 
   const addFeedMutation = useMutation({
     mutationFn: async (url: string) => {
-      const title = extractDomainFromUrl(url);
-      await api.feeds.add({
-        title,
-        url,
-        description: undefined,
-        category: undefined,
-      });
+      // The backend validates the URL, fills in the title and description
+      // from the feed, and fetches its first articles
+      try {
+        await api.feeds.add({ url });
+        return null;
+      } catch (error) {
+        // Already subscribed: the existing feed is returned to select it
+        if (error instanceof ApiError && error.data?.feed) {
+          return error.data.feed.id as string;
+        }
+        throw error;
+      }
     },
-    onSuccess: () => {
+    onSuccess: (existingFeedId) => {
       queryClient.invalidateQueries({ queryKey: ["feeds"] });
+      queryClient.invalidateQueries({ queryKey: ["articles"] });
+      if (existingFeedId) {
+        setSelectedFeedId(existingFeedId);
+      }
       setIsAdding(false);
       setNewFeedUrl("");
     },
   });
 
+  // A web page was given instead of a feed: the feeds it links to
+  const addFeedCandidates: string[] =
+    addFeedMutation.error instanceof ApiError
+      ? (addFeedMutation.error.data?.candidates ?? [])
+      : [];
+
   const deleteFeedMutation = useMutation({
     mutationFn: async (feedId: string) => {
       await api.feeds.delete(feedId);
@@ -151,19 +162,7 @@ export function Sidebar({
 
   const renameFeedMutation = useMutation({
     mutationFn: async ({ id, title }: { id: string; title: string }) => {
-      // Get current feed data
-      const feed = feeds.find((f) => f.id === id);
-      if (!feed) throw new Error("Feed not found");
-
-      // Delete and re-add with new title (simple approach)
-      // In production, you'd want an update_feed command
-      await api.feeds.delete(id);
-      await api.feeds.add({
-        title,
-        url: feed.url,
-        description: feed.description,
-        category: feed.category,
-      });
+      await api.feeds.update(id, { title });
     },
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["feeds"] });
@@ -307,13 +306,24 @@ export function Sidebar({
                       handleContextMenu(e, feed.id, feed.title)
                     }
                     className={cn(
-                      "w-full text-left px-3 py-2 rounded-md text-sm transition-colors truncate",
+                      "w-full text-left px-3 py-2 rounded-md text-sm transition-colors flex items-center gap-1",
                       selectedFeedId === feed.id
                         ? "bg-primary/10 text-primary font-medium"
                         : "hover:bg-muted",
                     )}
+                    title={feed.lastError || undefined}
                   >
-                    {feed.title}
+                    <span className="truncate">
+                      {(settings.showTranslatedTitles &&
+                        feed.titleTranslated) ||
+                        feed.title}
+                    </span>
+                    {feed.lastError && (
+                      <Icon
+                        icon="mdi:alert-circle"
+                        className="text-sm text-destructive flex-shrink-0"
+                      />
+                    )}
                   </button>
                 )}
               </div>
@@ -419,7 +429,22 @@ export function Sidebar({
 
               {addFeedMutation.isError && (
                 <div className="p-3 rounded text-sm bg-destructive/10 text-destructive">
-                  Failed to add feed. Please check the URL and try again.
+                  {addFeedMutation.error.message ||
+                    "Failed to add feed. Please check the URL and try again."}
+                  {addFeedCandidates.length > 0 && (
+                    <div className="mt-2 space-y-1 text-foreground">
+                      <p>Feeds found on this page:</p>
+                      {addFeedCandidates.map((candidate) => (
+                        <button
+                          key={candidate}
+                          onClick={() => addFeedMutation.mutate(candidate)}
+                          className="block w-full truncate text-left text-primary hover:underline"
+                        >
+                          {candidate}
+                        </button>
+                      ))}
+                    </div>
+                  )}
                 </div>
               )}
 
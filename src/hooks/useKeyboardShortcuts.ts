@@ -1,9 +1,15 @@
 import { useEffect, useCallback } from "react";
+import { useQueryClient } from "@tanstack/react-query";
 import { Article } from "@/stores/useAppStore";
+import { openLink } from "@/lib/links";
+import { api } from "@/lib/api";
 
 interface UseKeyboardShortcutsProps {
   articles: Article[];
-  selectedArticleId: string | null;
+  /** The open article, which may lie outside the loaded page */
+  selectedArticle: Article | null;
+  /** Scope for n/p unread navigation; all feeds when null */
+  feedId?: string | null;
   onSelectArticle: (article: Article | null) => void;
   onRefresh?: () => void;
   onToggleTheme?: () => void;
@@ -13,13 +19,17 @@ interface UseKeyboardShortcutsProps {
 
 export function useKeyboardShortcuts({
   articles,
-  selectedArticleId,
+  selectedArticle,
+  feedId,
   onSelectArticle,
   onRefresh,
   onToggleTheme,
   onToggleStar,
   onShowShortcuts,
 }: UseKeyboardShortcutsProps) {
+  const queryClient = useQueryClient();
+  const selectedArticleId = selectedArticle?.id ?? null;
+
   const navigate = useCallback(
     (direction: "up" | "down") => {
       if (!articles.length) return;
@@ -50,6 +60,27 @@ export function useKeyboardShortcuts({
     [articles, selectedArticleId, onSelectArticle],
   );
 
+  // Jump to the neighbouring unread article, even if it is not loaded
+  const navigateUnread = useCallback(
+    async (direction: "next" | "prev") => {
+      const target = selectedArticle
+        ? await api.articles.adjacentUnread(direction, {
+            feedId: feedId || undefined,
+            pubDate: selectedArticle.pubDate || 0,
+            id: selectedArticle.id,
+          })
+        : await api.articles.firstUnread({
+            feedId: feedId || undefined,
+            order: "newest",
+          });
+
+      if (target) {
+        onSelectArticle(target);
+      }
+    },
+    [selectedArticle, feedId, onSelectArticle],
+  );
+
   const handleKeyDown = useCallback(
     (event: KeyboardEvent) => {
       // Ignore if typing in input
@@ -71,14 +102,26 @@ export function useKeyboardShortcuts({
           event.preventDefault();
           navigate("up");
           break;
+        case "n":
+          event.preventDefault();
+          navigateUnread("next").catch(console.error);
+          break;
+        case "p":
+          event.preventDefault();
+          navigateUnread("prev").catch(console.error);
+          break;
         case "o":
         case "enter":
           event.preventDefault();
           if (selectedArticleId) {
-            window.open(
-              articles.find((a) => a.id === selectedArticleId)?.link,
-              "_blank",
-            );
+            const article = articles.find((a) => a.id === selectedArticleId);
+            if (article) {
+              openLink(article.link, article.id)
+                .then(() =>
+                  queryClient.invalidateQueries({ queryKey: ["articles"] }),
+                )
+                .catch(console.error);
+            }
           }
           break;
         case "r":
@@ -96,7 +139,7 @@ export function useKeyboardShortcuts({
             if (selectedArticleId) {
               const article = articles.find((a) => a.id === selectedArticleId);
               if (article && onToggleStar) {
-                onToggleStar(selectedArticleId, article.isStarred === 1);
+                onToggleStar(selectedArticleId, article.isStarred);
               }
             }
           }
@@ -111,10 +154,12 @@ export function useKeyboardShortcuts({
       articles,
       selectedArticleId,
       navigate,
+      navigateUnread,
       onRefresh,
       onToggleTheme,
       onToggleStar,
       onShowShortcuts,
+      queryClient,
     ],
   );
 
@@ -49,13 +49,43 @@ export async function getApiBaseUrl(): Promise<string> {
   return cachedApiBaseUrl;
 }
 
+/**
+ * Error carrying the HTTP status of a failed API request
+ */
+export class ApiError extends Error {
+  status: number;
+  /** Parsed JSON body of the error response, if any */
+  data?: any;
+
+  constructor(message: string, status: number, data?: any) {
+    super(message);
+    this.name = "ApiError";
+    this.status = status;
+    this.data = data;
+  }
+}
+
 export interface Feed {
   id: string;
   title: string;
+  titleTranslated?: string;
   url: string;
   description?: string;
   imageUrl?: string;
   category?: string;
+  preferSummary?: boolean;
+  headers?: Record<string, string>;
+  lastError?: string | null;
+  lastFetchedAt?: number | null;
+  lastFetchMs?: number | null;
+  language?: string | null;
+  refreshIntervalMinutes?: number | null;
+  sortOrder?: number | null;
+  /** HTTP basic auth user */
+  username?: string | null;
+  hasPassword?: boolean;
+  /** New articles arrive already read */
+  autoMarkRead?: boolean;
   createdAt: number;
   updatedAt: number;
 }
@@ -69,13 +99,130 @@ export interface Article {
   summary?: string;
   author?: string;
   pubDate: number | null;
-  isRead: number;
-  isStarred: number;
+  pubDateRaw?: string | null;
+  isRead: boolean;
+  isStarred: boolean;
+  isKept: boolean;
+  readAt?: number | null;
+  enclosureUrl?: string | null;
+  enclosureType?: string | null;
+  enclosureLength?: number | null;
+  imageUrl?: string | null;
+  wordCount?: number | null;
   fetchedAt: number;
+  updatedAt?: number | null;
+}
+
+export interface FeedResponseInfo {
+  finalUrl: string;
+  redirected: boolean;
+  status: number;
+  statusText: string;
+  contentType: string | null;
+  contentLength: number | null;
+  etag: string | null;
+  lastModified: string | null;
+  cacheControl: string | null;
+}
+
+export interface FeedPreview {
+  url: string;
+  title: string | null;
+  description: string | null;
+  siteUrl: string | null;
+  articleCount: number;
+}
+
+export interface HostStat {
+  host: string;
+  count: number;
+  feedIds: string[];
+  rateLimitRisk: boolean;
+}
+
+export interface Stats {
+  totalFeeds: number;
+  totalArticles: number;
+  unreadCount: number;
+  starredCount: number;
+  fetchedLast24h: number;
+}
+
+export interface RefreshResult {
+  feedId: string;
+  title: string;
+  newCount: number;
+  /** Ids of the articles saved by this refresh */
+  newIds: string[];
+  /** Stored articles updated after an edit at the source */
+  updated: number;
+  /** Malformed entries left out of the feed */
+  skipped: number;
+  error: string | null;
+}
+
+export interface DatabaseOptimization {
+  integrity: string;
+  sizeBefore: number;
+  sizeAfter: number;
+}
+
+export interface FeedHealth {
+  feedId: string;
+  title: string;
+  url: string;
+  status: number | null;
+  parses: boolean;
+  articleCount: number;
+  lastPubDate: number | null;
+  error: string | null;
+}
+
+export interface UnreadCount {
+  feedId: string;
+  count: number;
+}
+
+export interface FeedGroup {
+  /** The category, or "Uncategorized" for feeds without one */
+  name: string;
+  unreadCount: number;
+  feeds: (Feed & { unreadCount: number })[];
+}
+
+export interface TranslationConfigSummary {
+  provider: "openai" | "libretranslate" | "deepl";
+  model: string | null;
+  baseUrlHost: string | null;
+  hasApiKey: boolean;
+}
+
+export type FilterRuleField = "title" | "content" | "any";
+
+export type FilterRuleAction = "mark_read" | "star" | "delete";
+
+export interface FilterRule {
+  id: string;
+  pattern: string;
+  field: FilterRuleField;
+  action: FilterRuleAction;
+  feedId: string | null;
+  createdAt: number;
 }
 
+export type FilterRuleInput = Pick<
+  FilterRule,
+  "pattern" | "field" | "action" | "feedId"
+>;
+
 export type ArticleFilter = "all" | "unread" | "starred";
 
+export type ArticleSort =
+  | "date_desc"
+  | "date_asc"
+  | "fetched_desc"
+  | "title_asc";
+
 export const api = {
   feeds: {
     getAll: async (): Promise<Feed[]> => {
@@ -85,11 +232,115 @@ export const api = {
       return response.json();
     },
 
+    getTruncated: async (): Promise<string[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/truncated`);
+      if (!response.ok) throw new Error("Failed to detect truncated feeds");
+      return response.json();
+    },
+
+    /** Feeds by last fetch duration, slowest first */
+    getSlow: async (limit = 10): Promise<Feed[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/slow?limit=${limit}`);
+      if (!response.ok) throw new Error("Failed to fetch slow feeds");
+      return response.json();
+    },
+
+    /** Translate feed titles, into default_target_lang unless given */
+    translateTitles: async (
+      targetLang?: string,
+    ): Promise<{ count: number }> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/translate-titles`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ targetLang }),
+      });
+      if (!response.ok) throw new Error("Failed to translate feed titles");
+      return response.json();
+    },
+
+    discover: async (url: string): Promise<string[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(
+        `${baseUrl}/api/feeds/discover?${new URLSearchParams({ url })}`,
+      );
+      const data = await response.json();
+      if (!response.ok) {
+        throw new Error(data.error || "Failed to discover feeds");
+      }
+      return data;
+    },
+
+    inspect: async (url: string): Promise<FeedResponseInfo> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(
+        `${baseUrl}/api/feeds/inspect?${new URLSearchParams({ url })}`,
+      );
+      const data = await response.json();
+      if (!response.ok) throw new Error(data.error || "Failed to inspect feed");
+      return data;
+    },
+
+    preview: async (url: string): Promise<FeedPreview> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(
+        `${baseUrl}/api/feeds/preview?${new URLSearchParams({ url })}`,
+      );
+      const data = await response.json();
+      if (!response.ok) throw new Error(data.error || "Failed to preview feed");
+      return data;
+    },
+
+    analyzeHosts: async (): Promise<HostStat[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/hosts`);
+      if (!response.ok) throw new Error("Failed to analyze feed hosts");
+      return response.json();
+    },
+
+    getUnreadCounts: async (): Promise<UnreadCount[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/unread-counts`);
+      if (!response.ok) throw new Error("Failed to fetch unread counts");
+      return response.json();
+    },
+
+    checkHealth: async (): Promise<FeedHealth[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/health`);
+      if (!response.ok) throw new Error("Failed to check feeds");
+      return response.json();
+    },
+
+    getGrouped: async (): Promise<FeedGroup[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/grouped`);
+      if (!response.ok) throw new Error("Failed to fetch grouped feeds");
+      return response.json();
+    },
+
+    reorder: async (ids: string[]): Promise<void> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/reorder`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ ids }),
+      });
+      if (!response.ok) throw new Error("Failed to reorder feeds");
+    },
+
     add: async (data: {
-      title: string;
+      /** Read from the feed, which is then fetched right away, if omitted */
+      title?: string;
       url: string;
       description?: string;
       category?: string;
+      /** HTTP basic auth */
+      username?: string;
+      password?: string;
+      autoMarkRead?: boolean;
     }): Promise<Feed> => {
       const baseUrl = await getApiBaseUrl();
       const response = await fetch(`${baseUrl}/api/feeds`, {
@@ -97,7 +348,81 @@ export const api = {
         headers: { "Content-Type": "application/json" },
         body: JSON.stringify(data),
       });
-      if (!response.ok) throw new Error("Failed to add feed");
+      if (!response.ok) {
+        const result = await response.json().catch(() => ({}));
+        // A 409 carries the existing feed in result.feed
+        throw new ApiError(
+          result.error || "Failed to add feed",
+          response.status,
+          result,
+        );
+      }
+      return response.json();
+    },
+
+    importFile: async (data: {
+      path: string;
+      title: string;
+      category?: string;
+    }): Promise<{ feed: Feed; count: number }> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/import-file`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(data),
+      });
+      const result = await response.json();
+      if (!response.ok) {
+        throw new Error(result.error || "Failed to import feed file");
+      }
+      return result;
+    },
+
+    importUrls: async (text: string, category?: string): Promise<Feed[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/import-urls`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ text, category }),
+      });
+      const result = await response.json();
+      if (!response.ok) {
+        throw new Error(result.error || "Failed to import URLs");
+      }
+      return result;
+    },
+
+    update: async (
+      id: string,
+      data: {
+        title?: string;
+        url?: string;
+        description?: string | null;
+        category?: string | null;
+        preferSummary?: boolean;
+        headers?: Record<string, string> | null;
+        /** null uses the global auto-refresh interval */
+        refreshIntervalMinutes?: number | null;
+        /** null or "" removes basic auth */
+        username?: string | null;
+        /** Omit to keep the stored password; null or "" clears it */
+        password?: string | null;
+        autoMarkRead?: boolean;
+      },
+    ): Promise<Feed> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/${id}`, {
+        method: "PATCH",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(data),
+      });
+      if (!response.ok) {
+        const result = await response.json().catch(() => ({}));
+        throw new ApiError(
+          result.error || "Failed to update feed",
+          response.status,
+        );
+      }
       return response.json();
     },
 
@@ -109,12 +434,27 @@ export const api = {
       if (!response.ok) throw new Error("Failed to delete feed");
     },
 
+    clearTranslations: async (id: string): Promise<{ count: number }> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/feeds/${id}/translations`, {
+        method: "DELETE",
+      });
+      if (!response.ok) throw new Error("Failed to clear feed translations");
+      return response.json();
+    },
+
     refresh: async (
       id: string,
     ): Promise<{
       success: boolean;
       count: number;
       total?: number;
+      /** Entries skipped as invalid */
+      skipped?: number;
+      /** Ids of the new articles */
+      newIds?: string[];
+      /** Stored articles updated after an edit at the source */
+      updated?: number;
       title?: string;
       error?: string;
     }> => {
@@ -133,7 +473,13 @@ export const api = {
       return { ...data, success: true };
     },
 
-    refreshAll: async (): Promise<{ count: number; errors?: string[] }> => {
+    refreshAll: async (): Promise<{
+      count: number;
+      /** Ids of every new article */
+      newIds: string[];
+      errors?: string[];
+      results: RefreshResult[];
+    }> => {
       const baseUrl = await getApiBaseUrl();
 
       const controller = new AbortController();
@@ -175,6 +521,14 @@ export const api = {
       filter?: ArticleFilter;
       limit?: number;
       offset?: number;
+      /** Unix seconds; only articles published at or after this time */
+      after?: number;
+      /** Unix seconds; only articles published at or before this time */
+      before?: number;
+      /** Defaults to date_desc */
+      sort?: ArticleSort;
+      /** Search text matched against title and content */
+      q?: string;
     }): Promise<Article[]> => {
       const baseUrl = await getApiBaseUrl();
       const queryParams = new URLSearchParams();
@@ -182,12 +536,163 @@ export const api = {
       if (params.filter) queryParams.set("filter", params.filter);
       if (params.limit) queryParams.set("limit", params.limit.toString());
       if (params.offset) queryParams.set("offset", params.offset.toString());
+      if (params.after !== undefined) {
+        queryParams.set("after", params.after.toString());
+      }
+      if (params.before !== undefined) {
+        queryParams.set("before", params.before.toString());
+      }
+      if (params.sort) queryParams.set("sort", params.sort);
+      if (params.q) queryParams.set("q", params.q);
 
       const response = await fetch(`${baseUrl}/api/articles?${queryParams}`);
       if (!response.ok) throw new Error("Failed to fetch articles");
       return response.json();
     },
 
+    fetchPage: async (params: {
+      feedId?: string;
+      filter?: ArticleFilter;
+      /** nextCursor from the previous page; omit for the first page */
+      cursor?: string | null;
+      limit?: number;
+      q?: string;
+    }): Promise<{ articles: Article[]; nextCursor: string | null }> => {
+      const baseUrl = await getApiBaseUrl();
+      const queryParams = new URLSearchParams();
+      if (params.feedId) queryParams.set("feedId", params.feedId);
+      if (params.filter) queryParams.set("filter", params.filter);
+      if (params.cursor) queryParams.set("cursor", params.cursor);
+      if (params.limit) queryParams.set("limit", params.limit.toString());
+      if (params.q) queryParams.set("q", params.q);
+
+      const response = await fetch(
+        `${baseUrl}/api/articles/page?${queryParams}`,
+      );
+      if (!response.ok) throw new Error("Failed to fetch articles");
+      return response.json();
+    },
+
+    count: async (params: {
+      feedId?: string;
+      filter?: ArticleFilter;
+      after?: number;
+      before?: number;
+      q?: string;
+    }): Promise<number> => {
+      const baseUrl = await getApiBaseUrl();
+      const queryParams = new URLSearchParams();
+      if (params.feedId) queryParams.set("feedId", params.feedId);
+      if (params.filter) queryParams.set("filter", params.filter);
+      if (params.after) queryParams.set("after", params.after.toString());
+      if (params.before) queryParams.set("before", params.before.toString());
+      if (params.q) queryParams.set("q", params.q);
+
+      const response = await fetch(
+        `${baseUrl}/api/articles/count?${queryParams}`,
+      );
+      if (!response.ok) throw new Error("Failed to count articles");
+      const data = await response.json();
+      return data.count;
+    },
+
+    exportStarredMarkdown: async (): Promise<string> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/articles/starred/markdown`);
+      if (!response.ok) throw new Error("Failed to export starred articles");
+      return response.text();
+    },
+
+    fetchContextual: async (params: {
+      feedId?: string;
+      recentReadCount?: number;
+      limit?: number;
+    }): Promise<Article[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const queryParams = new URLSearchParams();
+      if (params.feedId) queryParams.set("feedId", params.feedId);
+      if (params.recentReadCount !== undefined) {
+        queryParams.set("recentReadCount", params.recentReadCount.toString());
+      }
+      if (params.limit) queryParams.set("limit", params.limit.toString());
+
+      const response = await fetch(
+        `${baseUrl}/api/articles/contextual?${queryParams}`,
+      );
+      if (!response.ok) throw new Error("Failed to fetch articles");
+      return response.json();
+    },
+
+    firstUnread: async (params: {
+      feedId?: string;
+      order?: "oldest" | "newest";
+    }): Promise<Article | null> => {
+      const baseUrl = await getApiBaseUrl();
+      const queryParams = new URLSearchParams();
+      if (params.feedId) queryParams.set("feedId", params.feedId);
+      if (params.order) queryParams.set("order", params.order);
+
+      const response = await fetch(
+        `${baseUrl}/api/articles/first-unread?${queryParams}`,
+      );
+      if (!response.ok) throw new Error("Failed to fetch first unread article");
+      return response.json();
+    },
+
+    getHighlighted: async (id: string): Promise<{ content: string }> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(
+        `${baseUrl}/api/articles/${id}/highlighted`,
+      );
+      if (!response.ok) throw new Error("Failed to fetch highlighted article");
+      return response.json();
+    },
+
+    /** A single article by id, or null if it doesn't exist */
+    get: async (id: string): Promise<Article | null> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/articles/${id}`);
+      if (!response.ok) throw new Error("Failed to fetch article");
+      return response.json();
+    },
+
+    /**
+     * The unread article after (next) or before (prev) the given one in
+     * newest-first order, or null at the end of the list
+     */
+    adjacentUnread: async (
+      direction: "next" | "prev",
+      params: { feedId?: string; pubDate: number; id: string },
+    ): Promise<Article | null> => {
+      const baseUrl = await getApiBaseUrl();
+      const queryParams = new URLSearchParams({
+        pubDate: params.pubDate.toString(),
+        id: params.id,
+      });
+      if (params.feedId) queryParams.set("feedId", params.feedId);
+
+      const response = await fetch(
+        `${baseUrl}/api/articles/${direction}-unread?${queryParams}`,
+      );
+      if (!response.ok) throw new Error("Failed to fetch unread article");
+      return response.json();
+    },
+
+    fetchFullContent: async (
+      id: string,
+    ): Promise<{ content: string; wordCount: number }> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(
+        `${baseUrl}/api/articles/${id}/full-content`,
+        { method: "POST" },
+      );
+      const data = await response.json();
+      if (!response.ok) {
+        throw new Error(data.error || "Failed to fetch full content");
+      }
+      return data;
+    },
+
     markRead: async (id: string, read: boolean): Promise<void> => {
       const baseUrl = await getApiBaseUrl();
       const response = await fetch(`${baseUrl}/api/articles/${id}/read`, {
@@ -198,6 +703,64 @@ export const api = {
       if (!response.ok) throw new Error("Failed to mark article as read");
     },
 
+    markSeen: async (ids: string[]): Promise<void> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/articles/mark-seen`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ ids }),
+      });
+      if (!response.ok) throw new Error("Failed to mark articles as seen");
+    },
+
+    markManyRead: async (ids: string[], read: boolean): Promise<number> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/articles/mark-read`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ ids, read }),
+      });
+      if (!response.ok) throw new Error("Failed to mark articles as read");
+      const data = await response.json();
+      return data.count;
+    },
+
+    starMany: async (ids: string[], starred: boolean): Promise<number> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/articles/star`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ ids, starred }),
+      });
+      if (!response.ok) throw new Error("Failed to star articles");
+      const data = await response.json();
+      return data.count;
+    },
+
+    delete: async (ids: string[]): Promise<number> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/articles/delete`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ ids }),
+      });
+      if (!response.ok) throw new Error("Failed to delete articles");
+      const data = await response.json();
+      return data.count;
+    },
+
+    cleanup: async (days: number, keepStarred = true): Promise<number> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/articles/cleanup`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ days, keepStarred }),
+      });
+      if (!response.ok) throw new Error("Failed to clean up articles");
+      const data = await response.json();
+      return data.count;
+    },
+
     toggleStarred: async (id: string, starred: boolean): Promise<void> => {
       const baseUrl = await getApiBaseUrl();
       const response = await fetch(`${baseUrl}/api/articles/${id}/starred`, {
@@ -207,6 +770,16 @@ export const api = {
       });
       if (!response.ok) throw new Error("Failed to toggle article star");
     },
+
+    setKept: async (id: string, kept: boolean): Promise<void> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/articles/${id}/kept`, {
+        method: "PATCH",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ kept }),
+      });
+      if (!response.ok) throw new Error("Failed to update article keep flag");
+    },
   },
 
   settings: {
@@ -226,12 +799,40 @@ export const api = {
       });
       if (!response.ok) throw new Error("Failed to set setting");
     },
+
+    /** Every setting, optionally without API keys and other secrets */
+    export: async (
+      options: { excludeSecrets?: boolean } = {},
+    ): Promise<Record<string, string>> => {
+      const baseUrl = await getApiBaseUrl();
+      const params = options.excludeSecrets ? "?excludeSecrets=true" : "";
+      const response = await fetch(`${baseUrl}/api/settings/export${params}`);
+      if (!response.ok) throw new Error("Failed to export settings");
+      return response.json();
+    },
+
+    import: async (
+      settings: Record<string, string>,
+    ): Promise<{ count: number }> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/settings/import`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(settings),
+      });
+      const data = await response.json();
+      if (!response.ok) {
+        throw new Error(data.error || "Failed to import settings");
+      }
+      return data;
+    },
   },
 
   translation: {
+    /** An empty targetLang uses the default_target_lang setting */
     translate: async (
       text: string,
-      targetLang: string = "zh",
+      targetLang: string = "",
     ): Promise<{ translatedText: string }> => {
       const baseUrl = await getApiBaseUrl();
       const response = await fetch(`${baseUrl}/api/translate`, {
@@ -243,21 +844,199 @@ export const api = {
       return response.json();
     },
 
-    save: async (articleId: string, content: string): Promise<void> => {
+    /**
+     * Translate text as the provider produces it, calling onText with the
+     * translation so far after each piece
+     * @returns The complete translation
+     */
+    translateStream: async (
+      text: string,
+      targetLang: string,
+      onText: (partial: string) => void,
+      signal?: AbortSignal,
+    ): Promise<string> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/translate/stream`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ text, targetLang }),
+        signal,
+      });
+      if (!response.ok || !response.body) {
+        const data = await response.json().catch(() => ({}));
+        throw new Error(data.error || "Translation failed");
+      }
+
+      // One JSON object per line: {text} for each piece, or {error}
+      const reader = response.body
+        .pipeThrough(new TextDecoderStream())
+        .getReader();
+      let buffer = "";
+      let translated = "";
+      for (;;) {
+        const { done, value } = await reader.read();
+        if (done) return translated;
+
+        buffer += value;
+        const lines = buffer.split("\n");
+        buffer = lines.pop()!;
+        for (const line of lines.filter(Boolean)) {
+          const message = JSON.parse(line);
+          if (message.error) throw new Error(message.error);
+          translated += message.text;
+          onText(translated);
+        }
+      }
+    },
+
+    translateArticles: async (
+      ids: string[],
+      targetLang: string = "",
+    ): Promise<{ id: string; title: string }[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/translate/articles`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ ids, targetLang }),
+      });
+      if (!response.ok) throw new Error("Failed to translate articles");
+      return response.json();
+    },
+
+    getConfigSummary: async (): Promise<TranslationConfigSummary> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/translate/config-summary`);
+      if (!response.ok) throw new Error("Failed to get translation config");
+      return response.json();
+    },
+
+    save: async (
+      articleId: string,
+      content: string,
+      targetLang?: string,
+    ): Promise<void> => {
       const baseUrl = await getApiBaseUrl();
       const response = await fetch(`${baseUrl}/api/translations/save`, {
         method: "POST",
         headers: { "Content-Type": "application/json" },
-        body: JSON.stringify({ articleId, content }),
+        body: JSON.stringify({ articleId, content, targetLang }),
       });
       if (!response.ok) throw new Error("Failed to save translation");
     },
 
-    get: async (articleId: string): Promise<{ content: string | null }> => {
+    get: async (
+      articleId: string,
+      targetLang?: string,
+    ): Promise<{ content: string | null }> => {
       const baseUrl = await getApiBaseUrl();
-      const response = await fetch(`${baseUrl}/api/translations/${articleId}`);
+      const query = targetLang ? `?targetLang=${targetLang}` : "";
+      const response = await fetch(
+        `${baseUrl}/api/translations/${articleId}${query}`,
+      );
       if (!response.ok) throw new Error("Failed to get translation");
       return response.json();
     },
   },
+
+  stats: {
+    get: async (): Promise<Stats> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/stats`);
+      if (!response.ok) throw new Error("Failed to get stats");
+      return response.json();
+    },
+  },
+
+  database: {
+    backup: async (path: string): Promise<{ path: string }> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/database/backup`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ path }),
+      });
+      const data = await response.json();
+      if (!response.ok) {
+        throw new Error(data.error || "Failed to back up database");
+      }
+      return data;
+    },
+
+    optimize: async (): Promise<DatabaseOptimization> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/database/optimize`, {
+        method: "POST",
+      });
+      const data = await response.json();
+      if (!response.ok) {
+        throw new Error(data.error || "Failed to optimize database");
+      }
+      return data;
+    },
+
+    restore: async (path: string): Promise<void> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/database/restore`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ path }),
+      });
+      if (!response.ok) {
+        const data = await response.json();
+        throw new Error(data.error || "Failed to restore database");
+      }
+    },
+  },
+
+  filterRules: {
+    getAll: async (feedId?: string): Promise<FilterRule[]> => {
+      const baseUrl = await getApiBaseUrl();
+      const query = feedId ? `?feedId=${encodeURIComponent(feedId)}` : "";
+      const response = await fetch(`${baseUrl}/api/filter-rules${query}`);
+      if (!response.ok) throw new Error("Failed to fetch filter rules");
+      return response.json();
+    },
+
+    create: async (rule: FilterRuleInput): Promise<FilterRule> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/filter-rules`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(rule),
+      });
+      if (!response.ok) {
+        const result = await response.json().catch(() => ({}));
+        throw new ApiError(
+          result.error || "Failed to create filter rule",
+          response.status,
+        );
+      }
+      return response.json();
+    },
+
+    update: async (id: string, rule: FilterRuleInput): Promise<FilterRule> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/filter-rules/${id}`, {
+        method: "PUT",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(rule),
+      });
+      if (!response.ok) {
+        const result = await response.json().catch(() => ({}));
+        throw new ApiError(
+          result.error || "Failed to update filter rule",
+          response.status,
+        );
+      }
+      return response.json();
+    },
+
+    delete: async (id: string): Promise<void> => {
+      const baseUrl = await getApiBaseUrl();
+      const response = await fetch(`${baseUrl}/api/filter-rules/${id}`, {
+        method: "DELETE",
+      });
+      if (!response.ok) throw new Error("Failed to delete filter rule");
+    },
+  },
 };
@@ -0,0 +1,25 @@
+/**
+ * Payload of the new-articles event, sent when a refresh saves unread
+ * articles; count leaves out those marked read on arrival
+ */
+export interface NewArticlesEvent {
+  feedId: string;
+  count: number;
+}
+
+/**
+ * Subscribe to an event forwarded from the backend by the Tauri shell.
+ * Outside Tauri (e.g. the browser dev server) this is a no-op.
+ * @returns A function that removes the listener
+ */
+export async function listenBackendEvent<T>(
+  name: string,
+  handler: (payload: T) => void,
+): Promise<() => void> {
+  if (typeof window === "undefined" || !("__TAURI_INTERNALS__" in window)) {
+    return () => {};
+  }
+
+  const { listen } = await import("@tauri-apps/api/event");
+  return listen<T>(name, (event) => handler(event.payload));
+}
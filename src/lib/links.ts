@@ -0,0 +1,20 @@
+import { api } from "@/lib/api";
+
+/**
+ * Open a URL in the system browser, falling back to a new window outside
+ * Tauri. When articleId is given the article is marked read as well, since
+ * opening the original counts as reading it.
+ */
+export async function openLink(url: string, articleId?: string) {
+  if (articleId) {
+    // Opening should not fail because the read state could not be saved
+    api.articles.markRead(articleId, true).catch(console.error);
+  }
+
+  if (typeof window !== "undefined" && "__TAURI_INTERNALS__" in window) {
+    const { openUrl } = await import("@tauri-apps/plugin-opener");
+    await openUrl(url);
+  } else {
+    window.open(url, "_blank");
+  }
+}
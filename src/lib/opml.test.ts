@@ -1,5 +1,6 @@
 import { describe, it, expect, vi } from "vitest";
 import { exportToOPML, importFromOPML, escapeXml } from "@/lib/opml";
+import { api, ApiError } from "@/lib/api";
 
 // Mock Tauri invoke
 vi.mock("@tauri-apps/api/core", () => ({
@@ -7,7 +8,8 @@ vi.mock("@tauri-apps/api/core", () => ({
 }));
 
 // Mock api module
-vi.mock("@/lib/api", () => ({
+vi.mock("@/lib/api", async (importOriginal) => ({
+  ...(await importOriginal<typeof import("@/lib/api")>()),
   api: {
     feeds: {
       add: vi.fn().mockResolvedValue({
@@ -17,6 +19,7 @@ vi.mock("@/lib/api", () => ({
         createdAt: Date.now(),
         updatedAt: Date.now(),
       }),
+      refresh: vi.fn().mockResolvedValue({ success: true, count: 3 }),
     },
   },
   getApiBaseUrl: vi.fn().mockResolvedValue("http://localhost:3456"),
@@ -80,6 +83,44 @@ describe("OPML Utilities", () => {
     });
   });
 
+  describe("exportToOPML categories", () => {
+    it("should group feeds by category", async () => {
+      const opml = await exportToOPML([
+        { ...mockFeeds[0], category: "Tech" },
+        { ...mockFeeds[1], category: "Tech" },
+        {
+          id: "feed-3",
+          title: "Loose Feed",
+          url: "https://loose.com/feed.xml",
+          createdAt: Date.now(),
+          updatedAt: Date.now(),
+        },
+      ]);
+
+      const doc = new DOMParser().parseFromString(opml, "text/xml");
+      const body = doc.querySelector("body")!;
+      const topLevel = Array.from(body.children);
+
+      expect(topLevel).toHaveLength(2);
+      expect(topLevel[0].getAttribute("xmlUrl")).toBe(
+        "https://loose.com/feed.xml",
+      );
+      expect(topLevel[1].getAttribute("text")).toBe("Tech");
+      expect(topLevel[1].children).toHaveLength(2);
+      expect(topLevel[1].children[0].getAttribute("type")).toBe("rss");
+    });
+
+    it("should round-trip categories through import", async () => {
+      const opml = await exportToOPML([{ ...mockFeeds[0], category: "Tech" }]);
+      vi.mocked(api.feeds.add).mockClear();
+
+      await importFromOPML(opml);
+      expect(api.feeds.add).toHaveBeenCalledWith(
+        expect.objectContaining({ url: mockFeeds[0].url, category: "Tech" }),
+      );
+    });
+  });
+
   describe("escapeXml", () => {
     it("should escape ampersands", () => {
       expect(escapeXml("A & B")).toBe("A &amp; B");
@@ -172,5 +213,89 @@ describe("OPML Utilities", () => {
       expect(result.count).toBe(2);
       expect(result.errors).toHaveLength(0);
     });
+
+    it("should fetch articles for imported feeds when requested", async () => {
+      const opml = `<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head><title>Test</title></head>
+  <body>
+    <outline type="rss" text="Feed 1" xmlUrl="https://feed1.com/rss"/>
+    <outline type="rss" text="Feed 2" xmlUrl="https://feed2.com/rss"/>
+  </body>
+</opml>`;
+      const onProgress = vi.fn();
+
+      const result = await importFromOPML(opml, {
+        fetchAfterImport: true,
+        onProgress,
+      });
+      expect(result.count).toBe(2);
+      expect(result.fetched).toBe(6);
+      expect(result.errors).toHaveLength(0);
+      expect(onProgress).toHaveBeenLastCalledWith({
+        phase: "fetching",
+        completed: 2,
+        total: 2,
+      });
+    });
+
+    it("should use the enclosing folder as the category", async () => {
+      const opml = `<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <body>
+    <outline text="Tech" title="Tech">
+      <outline type="rss" text="Feed 1" xmlUrl="https://feed1.com/rss"/>
+    </outline>
+    <outline type="rss" text="Feed 2" xmlUrl="https://feed2.com/rss"/>
+  </body>
+</opml>`;
+      vi.mocked(api.feeds.add).mockClear();
+
+      await importFromOPML(opml);
+      expect(api.feeds.add).toHaveBeenCalledWith(
+        expect.objectContaining({
+          url: "https://feed1.com/rss",
+          category: "Tech",
+        }),
+      );
+      expect(api.feeds.add).toHaveBeenCalledWith(
+        expect.objectContaining({
+          url: "https://feed2.com/rss",
+          category: undefined,
+        }),
+      );
+    });
+
+    it("should skip feeds that are already subscribed", async () => {
+      const opml = `<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <body>
+    <outline type="rss" text="Feed 1" xmlUrl="https://feed1.com/rss"/>
+    <outline type="rss" text="Feed 2" xmlUrl="https://feed2.com/rss"/>
+  </body>
+</opml>`;
+      vi.mocked(api.feeds.add).mockRejectedValueOnce(
+        new ApiError("Feed already exists", 409),
+      );
+
+      const result = await importFromOPML(opml);
+      expect(result.count).toBe(1);
+      expect(result.created).toHaveLength(1);
+      expect(result.skipped).toBe(1);
+      expect(result.errors).toHaveLength(0);
+    });
+
+    it("should not fetch articles by default", async () => {
+      const opml = `<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <body>
+    <outline type="rss" text="Feed 1" xmlUrl="https://feed1.com/rss"/>
+  </body>
+</opml>`;
+
+      const result = await importFromOPML(opml);
+      expect(result.count).toBe(1);
+      expect(result.fetched).toBe(0);
+    });
   });
 });
@@ -1,5 +1,6 @@
 import { Feed } from "@/stores/useAppStore";
-import { api } from "@/lib/api";
+import { api, ApiError } from "@/lib/api";
+import { downloadFile } from "@/lib/utils";
 
 export interface OPMLOutline {
   "@_text"?: string;
@@ -11,12 +12,30 @@ export interface OPMLOutline {
 }
 
 export async function exportToOPML(feeds: Feed[]): Promise<string> {
-  const outlines = feeds.map((feed) => ({
-    "@_text": feed.title,
-    "@_title": feed.title,
-    "@_xmlUrl": feed.url,
-    "@_type": "rss",
-  }));
+  const feedOutline = (feed: Feed, indent: string) =>
+    `${indent}<outline type="rss" text="${escapeXml(feed.title)}" title="${escapeXml(feed.title)}" xmlUrl="${escapeXml(feed.url)}"/>`;
+
+  // Feeds sharing a category are grouped under one folder outline
+  const categories = new Map<string, Feed[]>();
+  for (const feed of feeds) {
+    if (feed.category) {
+      categories.set(feed.category, [
+        ...(categories.get(feed.category) || []),
+        feed,
+      ]);
+    }
+  }
+
+  const lines = [
+    ...feeds
+      .filter((feed) => !feed.category)
+      .map((feed) => feedOutline(feed, "    ")),
+    ...[...categories].flatMap(([category, categoryFeeds]) => [
+      `    <outline text="${escapeXml(category)}" title="${escapeXml(category)}">`,
+      ...categoryFeeds.map((feed) => feedOutline(feed, "      ")),
+      "    </outline>",
+    ]),
+  ];
 
   return `<?xml version="1.0" encoding="UTF-8"?>
 <opml version="2.0">
@@ -24,16 +43,45 @@ export async function exportToOPML(feeds: Feed[]): Promise<string> {
     <title>RSS Reader Subscriptions</title>
   </head>
   <body>
-${outlines.map((o) => `    <outline type="rss" text="${escapeXml(o["@_text"] || "")}" title="${escapeXml(o["@_title"] || "")}" xmlUrl="${escapeXml(o["@_xmlUrl"] || "")}"/>`).join("\n")}
+${lines.join("\n")}
   </body>
 </opml>`;
 }
 
+export interface ImportProgress {
+  phase: "importing" | "fetching";
+  completed: number;
+  total: number;
+}
+
+export interface ImportOptions {
+  /** Refresh newly added feeds so they are populated right away */
+  fetchAfterImport?: boolean;
+  onProgress?: (progress: ImportProgress) => void;
+}
+
+export interface ImportResult {
+  /** Number of newly created feeds */
+  count: number;
+  created: Feed[];
+  /** Feeds skipped because their URL is already subscribed */
+  skipped: number;
+  errors: string[];
+  /** Articles fetched when fetchAfterImport is enabled */
+  fetched: number;
+}
+
+// Number of imported feeds refreshed at the same time
+const FETCH_CONCURRENCY = 4;
+
 export async function importFromOPML(
   opmlContent: string,
-): Promise<{ count: number; errors: string[] }> {
+  options: ImportOptions = {},
+): Promise<ImportResult> {
   const errors: string[] = [];
-  let count = 0;
+  const imported: Feed[] = [];
+  let skipped = 0;
+  let fetched = 0;
 
   try {
     // Use DOMParser to properly handle nested OPML structures
@@ -47,9 +95,14 @@ export async function importFromOPML(
     }
 
     // Recursively extract all RSS feeds from nested outline structures
-    const rssOutlines: Array<{ title: string; url: string }> = [];
+    const rssOutlines: Array<{
+      title: string;
+      url: string;
+      category?: string;
+    }> = [];
 
-    function extractOutlines(node: Element) {
+    // Folder outlines (no xmlUrl) become the category of the feeds they hold
+    function extractOutlines(node: Element, category?: string) {
       if (node.tagName === "outline") {
         const type = node.getAttribute("type");
         const xmlUrl = node.getAttribute("xmlUrl");
@@ -57,12 +110,13 @@ export async function importFromOPML(
           node.getAttribute("text") || node.getAttribute("title") || "Unknown";
 
         if (type === "rss" && xmlUrl) {
-          rssOutlines.push({ title: text, url: xmlUrl });
+          rssOutlines.push({ title: text, url: xmlUrl, category });
         }
 
         // Recursively process child outline elements
+        const childCategory = xmlUrl ? category : text;
         for (const child of Array.from(node.children)) {
-          extractOutlines(child);
+          extractOutlines(child, childCategory);
         }
       }
     }
@@ -81,24 +135,80 @@ export async function importFromOPML(
     }
 
     // Import extracted feeds
-    for (const { title, url } of rssOutlines) {
+    for (const [index, { title, url, category }] of rssOutlines.entries()) {
       try {
-        await api.feeds.add({
+        const feed = await api.feeds.add({
           title,
           url,
           description: undefined,
-          category: undefined,
+          category,
         });
-        count++;
+        imported.push(feed);
       } catch (e) {
-        errors.push(`Failed to import ${title}: ${e}`);
+        // The feeds.url UNIQUE constraint reports existing subscriptions
+        if (e instanceof ApiError && e.status === 409) {
+          skipped++;
+        } else {
+          errors.push(`Failed to import ${title}: ${e}`);
+        }
       }
+      options.onProgress?.({
+        phase: "importing",
+        completed: index + 1,
+        total: rssOutlines.length,
+      });
+    }
+
+    if (options.fetchAfterImport && imported.length > 0) {
+      fetched = await fetchImportedFeeds(imported, errors, options.onProgress);
     }
   } catch (e) {
     errors.push(`Failed to parse OPML: ${e}`);
   }
 
-  return { count, errors };
+  return {
+    count: imported.length,
+    created: imported,
+    skipped,
+    errors,
+    fetched,
+  };
+}
+
+// Refresh imported feeds with bounded concurrency; failures are reported in
+// errors without aborting the rest of the import
+async function fetchImportedFeeds(
+  feeds: Feed[],
+  errors: string[],
+  onProgress?: (progress: ImportProgress) => void,
+): Promise<number> {
+  let fetched = 0;
+  let completed = 0;
+  let next = 0;
+
+  const worker = async () => {
+    while (next < feeds.length) {
+      const feed = feeds[next++];
+      try {
+        const result = await api.feeds.refresh(feed.id);
+        if (result.success) {
+          fetched += result.count;
+        } else {
+          errors.push(`Failed to fetch ${feed.title}: ${result.error}`);
+        }
+      } catch (e) {
+        errors.push(`Failed to fetch ${feed.title}: ${e}`);
+      }
+      completed++;
+      onProgress?.({ phase: "fetching", completed, total: feeds.length });
+    }
+  };
+
+  await Promise.all(
+    Array.from({ length: Math.min(FETCH_CONCURRENCY, feeds.length) }, worker),
+  );
+
+  return fetched;
 }
 
 export function escapeXml(text: string): string {
@@ -115,13 +225,5 @@ export function downloadOPML(
   content: string,
   filename: string = "subscriptions.opml",
 ) {
-  const blob = new Blob([content], { type: "text/xml" });
-  const url = URL.createObjectURL(blob);
-  const a = document.createElement("a");
-  a.href = url;
-  a.download = filename;
-  document.body.appendChild(a);
-  a.click();
-  document.body.removeChild(a);
-  URL.revokeObjectURL(url);
+  downloadFile(content, filename, "text/xml");
 }
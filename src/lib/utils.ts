@@ -5,6 +5,21 @@ export function cn(...inputs: ClassValue[]) {
   return twMerge(clsx(inputs));
 }
 
+/**
+ * Save text as a file through a temporary download link
+ */
+export function downloadFile(content: string, filename: string, type: string) {
+  const blob = new Blob([content], { type });
+  const url = URL.createObjectURL(blob);
+  const a = document.createElement("a");
+  a.href = url;
+  a.download = filename;
+  document.body.appendChild(a);
+  a.click();
+  document.body.removeChild(a);
+  URL.revokeObjectURL(url);
+}
+
 export function formatDate(timestamp: number): string {
   const date = new Date(timestamp);
   return date.toLocaleDateString("en-US", {
@@ -14,6 +29,33 @@ export function formatDate(timestamp: number): string {
   });
 }
 
+// Average adult reading speed
+const WORDS_PER_MINUTE = 200;
+
+/**
+ * "5 min read" label for an article, or null when its length is unknown
+ */
+export function formatReadingTime(
+  wordCount: number | null | undefined,
+): string | null {
+  if (!wordCount) return null;
+  return `${Math.max(1, Math.round(wordCount / WORDS_PER_MINUTE))} min read`;
+}
+
+/**
+ * Human-readable file size, e.g. "1.5 MB"
+ */
+export function formatBytes(bytes: number): string {
+  const units = ["B", "KB", "MB", "GB"];
+  let value = bytes;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return `${unit === 0 ? value : value.toFixed(1)} ${units[unit]}`;
+}
+
 export function formatRelativeTime(timestamp: number): string {
   const date = new Date(timestamp);
   const now = new Date();
@@ -4,20 +4,39 @@ import { persist } from "zustand/middleware";
 export type Theme = "light" | "dark" | "system";
 export type ArticleFilter = "all" | "unread" | "starred";
 
+export type TranslationProvider = "openai" | "libretranslate" | "deepl";
+
 export interface TranslationSettings {
+  /** Empty means auto-detect from the base URL */
+  provider?: TranslationProvider | "";
   apiKey: string;
   baseUrl: string;
   model: string;
   prompt: string;
+  /** Show feed titles translated into the default language in the sidebar */
+  showTranslatedTitles?: boolean;
 }
 
 export interface Feed {
   id: string;
   title: string;
+  titleTranslated?: string;
   url: string;
   description?: string;
   imageUrl?: string;
   category?: string;
+  preferSummary?: boolean;
+  headers?: Record<string, string>;
+  lastError?: string | null;
+  lastFetchedAt?: number | null;
+  language?: string | null;
+  refreshIntervalMinutes?: number | null;
+  sortOrder?: number | null;
+  /** HTTP basic auth user */
+  username?: string | null;
+  hasPassword?: boolean;
+  /** New articles arrive already read */
+  autoMarkRead?: boolean;
   createdAt: number;
   updatedAt: number;
 }
@@ -31,8 +50,15 @@ export interface Article {
   summary?: string;
   author?: string;
   pubDate: number | null;
-  isRead: number;
-  isStarred: number;
+  isRead: boolean;
+  isStarred: boolean;
+  isKept: boolean;
+  readAt?: number | null;
+  enclosureUrl?: string | null;
+  enclosureType?: string | null;
+  enclosureLength?: number | null;
+  imageUrl?: string | null;
+  wordCount?: number | null;
   fetchedAt: number;
 }
 
@@ -100,10 +126,12 @@ export const useAppStore = create<AppState>()(
 
       // Settings
       settings: {
+        provider: "",
         apiKey: "",
         baseUrl: "https://libretranslate.com",
         model: "gpt-3.5-turbo",
         prompt: "Translate the following text to Chinese:",
+        showTranslatedTitles: false,
       },
       updateSettings: (newSettings) =>
         set((state) => ({